@@ -24,6 +24,12 @@ pub extern "C" fn _PG_init() {
             .set_library("pg_auto_dw")
             .enable_spi_access()
             .load();
+
+            BackgroundWorkerBuilder::new("Background Worker Scheduled Loads")
+            .set_function("background_worker_scheduled_loads")
+            .set_library("pg_auto_dw")
+            .enable_spi_access()
+            .load();
         }
         None => {
             log!("Database Name for this extension has not been set.");
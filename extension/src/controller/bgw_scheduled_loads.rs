@@ -0,0 +1,163 @@
+use pgrx::bgworkers::*;
+use pgrx::prelude::*;
+
+use std::time::Duration;
+use chrono::{NaiveDateTime, Timelike, Utc};
+
+use crate::queries;
+use crate::utility::guc;
+use crate::utility::cron::CronSchedule;
+use crate::controller::dv_builder;
+use crate::controller::dv_loader;
+
+// Minutes looked back over when the worker falls behind, such as after a long running load.
+const MAX_CATCH_UP_MINUTES: i64 = 60;
+
+struct LoadSchedule {
+    schema_name: String,
+    table_name: Option<String>,
+    cron: String,
+}
+
+#[pg_guard]
+#[no_mangle]
+pub extern "C" fn background_worker_scheduled_loads(_arg: pg_sys::Datum) {
+
+    let database_name_string = guc::get_guc(guc::PgAutoDWGuc::DatabaseName);
+    let database_name_o: Option<&str> = database_name_string.as_deref();
+
+    BackgroundWorker::attach_signal_handlers(SignalWakeFlags::SIGHUP | SignalWakeFlags::SIGTERM);
+    BackgroundWorker::connect_worker_to_spi(database_name_o, None);
+
+    // Schedules are evaluated once per minute, starting with the minute after the worker starts.
+    let mut last_checked_minute = current_minute();
+
+    while BackgroundWorker::wait_latch(Some(Duration::from_secs(10))) {
+
+        let now_minute = current_minute();
+        if now_minute <= last_checked_minute {
+            continue;
+        }
+
+        let catch_up_start = std::cmp::max(last_checked_minute, now_minute - chrono::Duration::minutes(MAX_CATCH_UP_MINUTES));
+        let due_minutes: Vec<NaiveDateTime> = (1..=(now_minute - catch_up_start).num_minutes())
+            .map(|minutes| catch_up_start + chrono::Duration::minutes(minutes))
+            .collect();
+        last_checked_minute = now_minute;
+
+        BackgroundWorker::transaction(|| {
+
+            let load_schedules = match get_load_schedules() {
+                Some(load_schedules) => load_schedules,
+                None => return,
+            };
+
+            for load_schedule in load_schedules {
+
+                let cron_schedule = match CronSchedule::parse(&load_schedule.cron) {
+                    Ok(cron_schedule) => cron_schedule,
+                    Err(e) => {
+                        log!("Scheduled load for {} skipped. {}", object_name(&load_schedule), e);
+                        continue;
+                    }
+                };
+
+                if due_minutes.iter().any(|due_minute| cron_schedule.matches(due_minute)) {
+                    run_scheduled_load(&load_schedule);
+                }
+            }
+        });
+    }
+}
+
+fn current_minute() -> NaiveDateTime {
+    let now = Utc::now().naive_utc();
+    now.with_second(0).and_then(|now| now.with_nanosecond(0)).unwrap_or(now)
+}
+
+fn object_name(load_schedule: &LoadSchedule) -> String {
+    match &load_schedule.table_name {
+        Some(table_name) => format!("{}.{}", load_schedule.schema_name, table_name),
+        None => load_schedule.schema_name.clone(),
+    }
+}
+
+// Schema and table cron context.  None when the extension has not been installed.
+fn get_load_schedules() -> Option<Vec<LoadSchedule>> {
+    Spi::connect(|client| {
+
+        let table_check = client.select("SELECT table_name FROM information_schema.tables WHERE table_schema = 'auto_dw' AND table_name = 'context'", None, None);
+        match table_check {
+            Ok(table_check) if table_check.len() > 0 => {},
+            _ => return None,
+        }
+
+        let mut load_schedules: Vec<LoadSchedule> = Vec::new();
+
+        match client.select(queries::LOAD_SCHEDULES, None, None) {
+            Ok(results) => {
+                for result in results {
+                    let schema_name = result.get_datum_by_ordinal(1).unwrap().value::<String>().unwrap().unwrap();
+                    let table_name = result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap();
+                    let cron = result.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap().unwrap();
+                    load_schedules.push(LoadSchedule { schema_name, table_name, cron });
+                }
+            }
+            Err(e) => {
+                log!("Error reading load schedules: {:?}", e);
+            }
+        }
+
+        Some(load_schedules)
+    })
+}
+
+// Loads the business keys and links sourced from the scheduled schema or table, then records the run's outcome.
+fn run_scheduled_load(load_schedule: &LoadSchedule) {
+
+    let started_at = Utc::now().naive_utc();
+
+    let (build_id, status, message) = match guc::get_guc(guc::PgAutoDWGuc::DwSchema) {
+        None => (None, "Failed", "DW SCHEMA GUC is not set.".to_string()),
+        Some(dw_schema) => match dv_loader::dv_load_latest_schema(&dw_schema) {
+            None => (None, "Failed", format!("No data warehouse build found for DW schema \"{}\".", dw_schema)),
+            Some((build_id, dv_schema)) => {
+                let object_dv_schema = match &load_schedule.table_name {
+                    Some(table_name) => dv_loader::dv_schema_filter_table(&dv_schema, &load_schedule.schema_name, table_name),
+                    None => dv_loader::dv_schema_filter_schema(&dv_schema, &load_schedule.schema_name),
+                };
+
+                if object_dv_schema.business_keys.is_empty() && object_dv_schema.links.is_empty() {
+                    (Some(build_id), "Skipped", format!("{} is not part of the build.", object_name(load_schedule)))
+                } else {
+                    let dv_dml = dv_loader::dv_data_loader_dml(&object_dv_schema) + &dv_loader::dv_pit_bridge_refresh_dml(&dv_schema);
+                    match dv_builder::try_execute(&dv_dml) {
+                        Ok(()) => (Some(build_id), "Succeeded", format!("Data pushed for {} business key(s).", object_dv_schema.business_keys.len())),
+                        Err(e) => (Some(build_id), "Failed", e),
+                    }
+                }
+            }
+        },
+    };
+
+    log!("Scheduled load for {}: {} | {}", object_name(load_schedule), status, message);
+
+    let finished_at = Utc::now().naive_utc();
+
+    Spi::connect(|mut client| {
+        let result = client.update(queries::INSERT_LOAD_RUN, None,
+            Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), load_schedule.schema_name.clone().into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), load_schedule.table_name.clone().into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), load_schedule.cron.clone().into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), build_id.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), status.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), message.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), started_at.to_string().into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), finished_at.to_string().into_datum()),
+            ]));
+        if let Err(e) = result {
+            log!("Error recording scheduled load run: {:?}", e);
+        }
+    });
+}
@@ -24,17 +24,11 @@ pub extern "C" fn background_worker_source_objects(_arg: pg_sys::Datum) {
                 match table_check_results {
                     Ok(table_check) => {
                         if table_check.len() > 0 {
+                            // 'a^' matches nothing, so only changes to columns already included are picked up.
                             client.update(
-                                queries::source_object_dw(
-                                    "a^", 
-                                    "a^", 
-                                    "a^", 
-                                    "a^", 
-                                    "a^", 
-                                    "a^"
-                                ).as_str(),
-                                None,
+                                queries::SOURCE_OBJECT_DW,
                                 None,
+                                Some(vec!["a^"; 6].into_iter().map(|pattern| (PgOid::from(pg_sys::TEXTOID), pattern.into_datum())).collect()),
                             )?;
                         } else {
                             panic!("TABLE AUTO_DW.SOURCE_OBJECTS not found. PG_AUTO_DW Extension may need to be installed.");
@@ -12,6 +12,7 @@ use crate::utility::guc;
 use regex::Regex;
 
 const MAX_TRANSFORMER_RETRIES: u8 = 3; // TODO: Set in GUC
+const CONTEXT_OVERRIDE_REASON: &str = "Set by context from auto_dw.update_context.";
 
 #[pg_guard]
 #[no_mangle]
@@ -65,6 +66,9 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
 
                 let columns = extract_column_numbers(&table_details_json_str);
 
+                let column_contexts = get_column_contexts(source_table_prompt.key);
+                let manual_overrides = get_manual_overrides(source_table_prompt.key);
+
                 // Table Business Key Component Identification
                 let mut generation_json_business_key_component_identification: Option<serde_json::Value> = None;
                 let mut generation_json_business_key_name: Option<serde_json::Value> = None;
@@ -73,8 +77,28 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
 
                 // Evaluate Attributes
                 for column in &columns {
+                    // Manual overrides are never reclassified.
+                    if manual_overrides.contains_key(column) {
+                        continue;
+                    }
+
+                    let column_context = column_contexts.get(column);
+
+                    // Context overrides skip the transformer.
+                    if let Some(is_business_key) = column_context.and_then(|column_context| column_context.is_business_key) {
+                        business_key_component_identification.insert(column, BusinessKeyComponentIdentification {
+                            business_key_component_identification: BusinessKeyComponentIdentificationValues {
+                                is_business_key_component: is_business_key,
+                                confidence_value: 1.0,
+                                reason: CONTEXT_OVERRIDE_REASON.to_string(),
+                            }
+                        });
+                        continue;
+                    }
+
+                    let context_hint = get_context_hint(column_context);
                     let mut retries = 0;
-                    let mut hints = String::new();
+                    let mut hints = context_hint.clone();
 
                     while retries < MAX_TRANSFORMER_RETRIES {
                         runtime.block_on(async {
@@ -89,7 +113,7 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                                 },
                                 Err(e) => {
                                     log!("Error in transformer request, malformed or timed out: {}", e);
-                                    hints = format!("Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", retries + 1);
+                                    hints = format!("{}Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", context_hint, retries + 1);
                                     None
                                 }
                             };
@@ -116,8 +140,28 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
 
                 // Generate Name if Identified as BK
                 for column in &columns {
+                    // Manual overrides are never reclassified.
+                    if manual_overrides.contains_key(column) {
+                        continue;
+                    }
+
+                    let column_context = column_contexts.get(column);
+
+                    // Context overrides skip the transformer.
+                    if let Some(name) = column_context.and_then(|column_context| column_context.business_key_name.clone()) {
+                        business_key_name.insert(column, BusinessKeyName {
+                            business_key_name_values: BusinessKeyNameValues {
+                                name,
+                                confidence_value: 1.0,
+                                reason: CONTEXT_OVERRIDE_REASON.to_string(),
+                            }
+                        });
+                        continue;
+                    }
+
+                    let context_hint = get_context_hint(column_context);
                     let mut retries = 0;
-                    let mut hints = String::new();
+                    let mut hints = context_hint.clone();
 
                     match business_key_component_identification.get(column) {
                         Some(bkci) => {
@@ -132,7 +176,7 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                                             },
                                             Err(e) => {
                                                 log!("Error in transformer request, malformed or timed out: {}", e);
-                                                hints = format!("Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", retries + 1);
+                                                hints = format!("{}Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", context_hint, retries + 1);
                                                 None
                                             }
                                           };
@@ -168,8 +212,28 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                 let mut descriptors_sensitive: HashMap<&u32, DescriptorSensitive> = HashMap::new();
                 let mut generation_json_descriptor_sensitive: Option<serde_json::Value> = None;
                 for column in &columns {
+                    // Manual overrides are never reclassified.
+                    if manual_overrides.contains_key(column) {
+                        continue;
+                    }
+
+                    let column_context = column_contexts.get(column);
+
+                    // Context overrides skip the transformer.
+                    if let Some(is_sensitive) = column_context.and_then(|column_context| column_context.is_sensitive) {
+                        descriptors_sensitive.insert(column, DescriptorSensitive {
+                            descriptor_sensitive_values: DescriptorSensitiveValues {
+                                is_pii: is_sensitive,
+                                confidence_value: 1.0,
+                                reason: CONTEXT_OVERRIDE_REASON.to_string(),
+                            }
+                        });
+                        continue;
+                    }
+
+                    let context_hint = get_context_hint(column_context);
                     let mut retries = 0;
-                    let mut hints = String::new();
+                    let mut hints = context_hint.clone();
                     while retries < MAX_TRANSFORMER_RETRIES {   
                     // Run the async block
                         runtime.block_on(async {
@@ -185,7 +249,7 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                                 },
                                 Err(e) => {
                                     log!("Error in transformer request, malformed or timed out: {}", e);
-                                    hints = format!("Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", retries + 1);
+                                    hints = format!("{}Hint: Please ensure you provide a JSON response only.  This is your {} attempt.", context_hint, retries + 1);
                                     None
                                 }
                             };
@@ -215,11 +279,31 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                 let table_column_links = table_column_links_o.unwrap();
 
                // Build the SQL INSERT statement
-                let mut insert_sql = String::from("INSERT INTO auto_dw.transformer_responses (fk_source_objects, model_name, category, business_key_name, confidence_score, reason) VALUES ");
+                let mut insert_values: Vec<TransformerResponse> = Vec::new();
 
-                for (index, column) in columns.iter().enumerate() {
+                for column in columns.iter() {
 
-                    let last = {index == table_column_links.column_links.len() - 1};
+                    // Manual overrides are carried forward to the current version of the column.
+                    if let Some(manual_override) = manual_overrides.get(column) {
+                        let pk_source_objects = match table_column_links.find_pk_source_objects(column.clone() as i32) {
+                            Some(pk_source_objects) => pk_source_objects,
+                            None => {
+                                warning!("Manual override of column_ordinal_position {} was not carried forward, as the column was not found.", column);
+                                continue;
+                            }
+                        };
+
+                        insert_values.push(TransformerResponse {
+                            pk_source_objects,
+                            model_name: manual_override.model_name.clone(),
+                            category: manual_override.category.clone(),
+                            business_key_name: manual_override.business_key_name.clone(),
+                            confidence_score: 1.0,
+                            reason: manual_override.reason.clone(),
+                            manual_override: true,
+                        });
+                        continue;
+                    }
 
                     match (business_key_component_identification.get(column), business_key_name.get(column)) {
                         (Some(business_key_component_identification), Some(business_key_name)) => {
@@ -247,11 +331,15 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                                 panic!()
                             }
     
-                            if !last {
-                                insert_sql.push_str(&format!("({}, '{}', '{}', '{}', {}, '{}'),", pk_source_objects, model_name, category, bk_name.replace(" ", "_"), confidence_score, reason.replace("'", "''")));
-                            } else {
-                                insert_sql.push_str(&format!("({}, '{}', '{}', '{}', {}, '{}');", pk_source_objects, model_name, category, bk_name.replace(" ", "_"), confidence_score, reason.replace("'", "''")));
-                            }
+                            insert_values.push(TransformerResponse {
+                                pk_source_objects,
+                                model_name: model_name.to_string(),
+                                category: category.to_string(),
+                                business_key_name: bk_name.replace(" ", "_"),
+                                confidence_score,
+                                reason,
+                                manual_override: false,
+                            });
         
                         }
                         _ => { // Not Identified as BKs
@@ -280,19 +368,33 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
                                 log!("Teseting Can't find a response for {} in Descriptors Sensitive Hashmap.", column);
                             }
     
-                            if !last {
-                                insert_sql.push_str(&format!("({}, '{}', '{}', '{}', {}, '{}'),", pk_source_objects, model_name, category, bk_name.replace(" ", "_"), confidence_score, reason.replace("'", "''")));
-                            } else {
-                                insert_sql.push_str(&format!("({}, '{}', '{}', '{}', {}, '{}');", pk_source_objects, model_name, category, bk_name.replace(" ", "_"), confidence_score, reason.replace("'", "''")));
-                            }
+                            insert_values.push(TransformerResponse {
+                                pk_source_objects,
+                                model_name: model_name.to_string(),
+                                category: category.to_string(),
+                                business_key_name: bk_name.replace(" ", "_"),
+                                confidence_score,
+                                reason,
+                                manual_override: false,
+                            });
                         }
                     }
                 }
                 
-                // Push Generation to TABLE TRANSFORMER_RESPONSES 
+                // Push Generation to TABLE TRANSFORMER_RESPONSES - Values are bound, as model reasoning may hold any text.
                 BackgroundWorker::transaction(|| {
                     Spi::connect(|mut client| {
-                        _ = client.update(insert_sql.as_str(), None, None);
+                        for transformer_response in insert_values {
+                            _ = client.update(queries::INSERT_TRANSFORMER_RESPONSE, None, Some(vec![
+                                (PgOid::from(pg_sys::INT4OID), transformer_response.pk_source_objects.into_datum()),
+                                (PgOid::from(pg_sys::TEXTOID), transformer_response.model_name.into_datum()),
+                                (PgOid::from(pg_sys::TEXTOID), transformer_response.category.into_datum()),
+                                (PgOid::from(pg_sys::TEXTOID), transformer_response.business_key_name.into_datum()),
+                                (PgOid::from(pg_sys::FLOAT8OID), transformer_response.confidence_score.into_datum()),
+                                (PgOid::from(pg_sys::TEXTOID), transformer_response.reason.into_datum()),
+                                (PgOid::from(pg_sys::BOOLOID), transformer_response.manual_override.into_datum()),
+                            ]));
+                        }
                     })
                 });
         }
@@ -300,20 +402,118 @@ pub extern "C" fn background_worker_transformer_client(_arg: pg_sys::Datum) {
     }
 }
 
-fn extension_log(process: &str, level: &str, message: &str) {
-
-    let insert_statement = format!(r#"
-                                            INSERT INTO auto_dw.log (process, level, message)
-                                            VALUES ('{}', '{}', '{}');
-                                        "#, process, level, message);
+// A row for TABLE TRANSFORMER_RESPONSES.
+struct TransformerResponse {
+    pk_source_objects: i32,
+    model_name: String,
+    category: String,
+    business_key_name: String,
+    confidence_score: f64,
+    reason: String,
+    manual_override: bool,
+}
 
+fn extension_log(process: &str, level: &str, message: &str) {
     BackgroundWorker::transaction(|| {
         Spi::connect(|mut client| {
-            _ = client.update(insert_statement.as_str(), None, None);
+            _ = client.update(queries::INSERT_LOG, None, Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), process.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), level.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), message.into_datum()),
+            ]));
         })
     });
 }
 
+// Context set with auto_dw.update_context for a source column.
+struct ColumnContext {
+    hint: Option<String>,
+    is_business_key: Option<bool>,
+    business_key_name: Option<String>,
+    is_sensitive: Option<bool>,
+}
+
+fn get_column_contexts(table_oid: u32) -> HashMap<u32, ColumnContext> {
+    BackgroundWorker::transaction(|| {
+        Spi::connect(|client| {
+            let mut column_contexts: HashMap<u32, ColumnContext> = HashMap::new();
+
+            let results = client.select(queries::SOURCE_COLUMN_CONTEXT, None,
+                Some(vec![
+                    (PgOid::from(pg_sys::INT8OID), (table_oid as i64).into_datum()),
+                ]));
+
+            match results {
+                Ok(results) => {
+                    for result in results {
+                        let column_ordinal_position = result.get_datum_by_ordinal(1).unwrap().value::<i32>().unwrap().unwrap() as u32;
+                        let column_context = ColumnContext {
+                            hint: result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap(),
+                            is_business_key: result.get_datum_by_ordinal(3).unwrap().value::<bool>().unwrap(),
+                            business_key_name: result.get_datum_by_ordinal(4).unwrap().value::<String>().unwrap(),
+                            is_sensitive: result.get_datum_by_ordinal(5).unwrap().value::<bool>().unwrap(),
+                        };
+                        column_contexts.insert(column_ordinal_position, column_context);
+                    }
+                }
+                Err(e) => {
+                    log!("Error reading source column context: {:?}", e);
+                }
+            }
+
+            column_contexts
+        })
+    })
+}
+
+// Category set with auto_dw.set_column_category for a source column.
+struct ManualOverride {
+    model_name: String,
+    category: String,
+    business_key_name: String,
+    reason: String,
+}
+
+fn get_manual_overrides(table_oid: u32) -> HashMap<u32, ManualOverride> {
+    BackgroundWorker::transaction(|| {
+        Spi::connect(|client| {
+            let mut manual_overrides: HashMap<u32, ManualOverride> = HashMap::new();
+
+            let results = client.select(queries::MANUAL_OVERRIDE_COLUMNS, None,
+                Some(vec![
+                    (PgOid::from(pg_sys::INT8OID), (table_oid as i64).into_datum()),
+                ]));
+
+            match results {
+                Ok(results) => {
+                    for result in results {
+                        let column_ordinal_position = result.get_datum_by_ordinal(1).unwrap().value::<i32>().unwrap().unwrap() as u32;
+                        let manual_override = ManualOverride {
+                            model_name: result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap().unwrap_or_default(),
+                            category: result.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap().unwrap_or_default(),
+                            business_key_name: result.get_datum_by_ordinal(4).unwrap().value::<String>().unwrap().unwrap_or_default(),
+                            reason: result.get_datum_by_ordinal(5).unwrap().value::<String>().unwrap().unwrap_or_default(),
+                        };
+                        manual_overrides.insert(column_ordinal_position, manual_override);
+                    }
+                }
+                Err(e) => {
+                    log!("Error reading source column manual overrides: {:?}", e);
+                }
+            }
+
+            manual_overrides
+        })
+    })
+}
+
+fn get_context_hint(column_context: Option<&ColumnContext>) -> String {
+    match column_context.and_then(|column_context| column_context.hint.as_ref()) {
+        Some(hint) => format!("Hint: {}  ", hint),
+        None => String::new(),
+    }
+}
+
 fn extract_column_numbers(json_str: &str) -> Vec<u32> {
     // Define a regex to capture the column numbers
     let re = Regex::new(r"Column No: (\d+)").expect("Invalid regex");
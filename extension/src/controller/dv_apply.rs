@@ -0,0 +1,229 @@
+use pgrx::prelude::*;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::model::dv_schema::*;
+
+use super::dv_builder::{deploy_dv, get_column_data, get_previous_dv_schema};
+
+// Deploys a DVSchema edited by hand as a new build, returning its build ID.  The schema replaces the model
+// proposed by go, so it is validated and its source columns checked against the catalog before anything is built.
+pub fn dv_apply_schema(schema_json: serde_json::Value) -> String {
+    let mut dv_schema: DVSchema = serde_json::from_value(schema_json)
+        .unwrap_or_else(|e| error!("Schema is not a valid DVSchema: {}", e));
+
+    let mut problems = validate_dv_schema(&dv_schema);
+
+    for column_data in source_columns_mut(&mut dv_schema) {
+        match get_column_data(&column_data.schema_name, &column_data.table_name, &column_data.column_name) {
+            Some(catalog_column_data) => {
+                column_data.system_id = catalog_column_data.system_id;
+                column_data.table_oid = catalog_column_data.table_oid;
+                column_data.column_ordinal_position = catalog_column_data.column_ordinal_position;
+                column_data.column_type_name = catalog_column_data.column_type_name;
+            }
+            None => problems.push(format!("Source column \"{}.{}.{}\" does not exist.", column_data.schema_name, column_data.table_name, column_data.column_name)),
+        }
+    }
+
+    let dw_schema_exists = Spi::get_one_with_args::<bool>(
+        "SELECT EXISTS (SELECT 1 FROM pg_catalog.pg_namespace WHERE nspname = $1)",
+        vec![(PgOid::from(pg_sys::TEXTOID), dv_schema.dw_schema.clone().into_datum())],
+    );
+    if !matches!(dw_schema_exists, Ok(Some(true))) {
+        problems.push(format!("DW schema \"{}\" does not exist.", dv_schema.dw_schema));
+    }
+
+    if !problems.is_empty() {
+        error!("Schema could not be applied:\n{}", problems.join("\n"));
+    }
+
+    let now_gmt = Utc::now().naive_utc();
+    if dv_schema.create_timestamp_gmt == Default::default() {
+        dv_schema.create_timestamp_gmt = now_gmt;
+    }
+
+    let previous_dv_schema = get_previous_dv_schema(&dv_schema);
+
+    let build_id = Uuid::new_v4().to_string();
+    deploy_dv(&build_id, dv_schema, previous_dv_schema);
+
+    build_id
+}
+
+// Problems with a DVSchema that would keep it from being built or loaded.  Hubs are loaded from every table holding
+// all of their business key parts, links from a single source table, and satellites from a source table of their parent.
+fn validate_dv_schema(dv_schema: &DVSchema) -> Vec<String> {
+    let naming = &dv_schema.naming;
+    let mut problems: Vec<String> = Vec::new();
+
+    if dv_schema.dw_schema.trim().is_empty() {
+        problems.push("DW Schema is empty.".to_string());
+    }
+
+    let templates = [
+        ("Hub", &naming.hub), ("Link", &naming.link), ("Satellite", &naming.satellite), ("Sensitive", &naming.sensitive),
+        ("PIT", &naming.pit), ("Bridge", &naming.bridge), ("Current View", &naming.current_view), ("Dimension", &naming.dimension),
+        ("Fact", &naming.fact), ("Hash Key", &naming.hash_key), ("Hash Diff", &naming.hash_diff), ("Business Key", &naming.business_key),
+        ("Surrogate Key", &naming.surrogate_key),
+    ];
+    for (key, template) in templates {
+        if !template.contains("{name}") && !template.contains("{table}") {
+            problems.push(format!("Naming template \"{}\" must contain {{name}} or {{table}}.", key));
+        }
+    }
+
+    if dv_schema.business_keys.is_empty() {
+        problems.push("Schema has no business keys.".to_string());
+    }
+
+    // Satellite tables along with the hub or link they belong to.
+    let mut satellite_parents: Vec<(String, String)> = Vec::new();
+
+    for (index, business_key) in dv_schema.business_keys.iter().enumerate() {
+        let business_key_label = format!("Business key \"{}\"", business_key.name);
+
+        if business_key.name.trim().is_empty() {
+            problems.push(format!("Business key {} has no name.", index + 1));
+        }
+        if dv_schema.business_keys[..index].iter().any(|other| other.name == business_key.name) {
+            problems.push(format!("{} is defined more than once.", business_key_label));
+        }
+        if business_key.business_key_part_links.is_empty() {
+            problems.push(format!("{} has no business key parts.", business_key_label));
+        }
+
+        for (part_index, part_link) in business_key.business_key_part_links.iter().enumerate() {
+            if business_key.business_key_part_links[..part_index].iter().any(|other| other.alias == part_link.alias) {
+                problems.push(format!("{} has more than one business key part with alias \"{}\".", business_key_label, part_link.alias));
+            }
+            if part_link.source_columns.is_empty() {
+                problems.push(format!("{} part \"{}\" has no source column.", business_key_label, part_link.alias));
+            }
+        }
+
+        let business_key_source_tables = business_key.source_tables();
+        for (schema_name, table_name) in &business_key_source_tables {
+            if business_key.source_columns_of(schema_name, table_name).is_none() {
+                problems.push(format!("{} is loaded from \"{}.{}\", which does not source every business key part.", business_key_label, schema_name, table_name));
+            }
+        }
+
+        let mut descriptor_source_tables: Vec<(&str, &str)> = Vec::new();
+        problems.extend(validate_descriptors(naming, &business_key_label, &naming.hub(&business_key.name), &naming.hub_hash_key(&business_key.name), &business_key.descriptors, &mut descriptor_source_tables, &mut satellite_parents));
+        for (schema_name, table_name) in descriptor_source_tables {
+            if !business_key_source_tables.iter().any(|(source_schema_name, source_table_name)| source_schema_name == schema_name && source_table_name == table_name) {
+                problems.push(format!("{} has descriptors from \"{}.{}\", which does not source its business key.", business_key_label, schema_name, table_name));
+                break;
+            }
+        }
+    }
+
+    for (index, link) in dv_schema.links.iter().enumerate() {
+        let link_label = format!("Link \"{}\"", link.name);
+
+        if dv_schema.links[..index].iter().any(|other| other.name == link.name) {
+            problems.push(format!("{} is defined more than once.", link_label));
+        }
+        if link.hub_references.is_empty() {
+            problems.push(format!("{} has no hub references.", link_label));
+        }
+
+        let mut source_tables: Vec<(&str, &str)> = Vec::new();
+        for hub_reference in &link.hub_references {
+            if !dv_schema.business_keys.iter().any(|business_key| business_key.name == hub_reference.business_key_name) {
+                problems.push(format!("{} references business key \"{}\", which is not defined.", link_label, hub_reference.business_key_name));
+            }
+            if hub_reference.source_columns.is_empty() {
+                problems.push(format!("{} reference to \"{}\" has no source columns.", link_label, hub_reference.business_key_name));
+            }
+            for source_column in &hub_reference.source_columns {
+                source_tables.push((&source_column.schema_name, &source_column.table_name));
+            }
+        }
+
+        problems.extend(validate_descriptors(naming, &link_label, &naming.link(&link.name), &naming.link_hash_key(&link.name), &link.descriptors, &mut source_tables, &mut satellite_parents));
+        problems.extend(validate_single_source_table(&link_label, &source_tables));
+    }
+
+    problems
+}
+
+fn validate_descriptors<'a>(naming: &Naming, parent_label: &str, parent_name: &str, parent_hash_key: &str, descriptors: &'a Vec<Descriptor>, source_tables: &mut Vec<(&'a str, &'a str)>, satellite_parents: &mut Vec<(String, String)>) -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+
+    // Satellites along with the table they are loaded from.
+    let mut satellite_source_tables: Vec<(String, (&str, &str))> = Vec::new();
+
+    for (index, descriptor) in descriptors.iter().enumerate() {
+        let alias = &descriptor.descriptor_link.alias;
+        let satellite_name = naming.descriptor_satellite(descriptor);
+
+        if descriptor.orbit.trim().is_empty() {
+            problems.push(format!("{} descriptor \"{}\" has no orbit.", parent_label, alias));
+        }
+        if descriptors[..index].iter().any(|other| naming.descriptor_satellite(other) == satellite_name && &other.descriptor_link.alias == alias) {
+            problems.push(format!("{} has more than one descriptor \"{}\" in satellite \"{}\".", parent_label, alias, satellite_name));
+        }
+        let reserved_columns = [parent_hash_key.to_string(), "load_ts".to_string(), "record_source".to_string(), naming.descriptor_satellite_hash_diff(descriptor)];
+        if reserved_columns.contains(alias) {
+            problems.push(format!("{} descriptor alias \"{}\" is reserved for satellite \"{}\".", parent_label, alias, satellite_name));
+        }
+        match &descriptor.descriptor_link.source_column {
+            Some(source_column) => {
+                let source_table = (source_column.schema_name.as_str(), source_column.table_name.as_str());
+                source_tables.push(source_table);
+                match satellite_source_tables.iter().find(|(name, _)| name == &satellite_name) {
+                    Some((_, (schema_name, table_name))) if (*schema_name, *table_name) != source_table => {
+                        problems.push(format!("{} descriptor \"{}\" is from \"{}.{}\", but satellite \"{}\" is loaded from \"{}.{}\".", parent_label, alias, source_table.0, source_table.1, satellite_name, schema_name, table_name));
+                    }
+                    Some(_) => {}
+                    None => satellite_source_tables.push((satellite_name.clone(), source_table)),
+                }
+            }
+            None => problems.push(format!("{} descriptor \"{}\" has no source column.", parent_label, alias)),
+        }
+
+        match satellite_parents.iter().find(|(name, _)| name == &satellite_name) {
+            Some((_, satellite_parent)) if satellite_parent != parent_name => {
+                problems.push(format!("{} descriptor \"{}\" is in satellite \"{}\", which belongs to \"{}\".", parent_label, alias, satellite_name, satellite_parent));
+            }
+            Some(_) => {}
+            None => satellite_parents.push((satellite_name, parent_name.to_string())),
+        }
+    }
+
+    problems
+}
+
+fn validate_single_source_table(parent_label: &str, source_tables: &Vec<(&str, &str)>) -> Option<String> {
+    let (schema_name, table_name) = source_tables.first()?;
+    source_tables
+        .iter()
+        .find(|(other_schema_name, other_table_name)| other_schema_name != schema_name || other_table_name != table_name)
+        .map(|(other_schema_name, other_table_name)| format!("{} is loaded from \"{}.{}\", but also references \"{}.{}\".  Links, along with their descriptors, are loaded from a single source table.", parent_label, schema_name, table_name, other_schema_name, other_table_name))
+}
+
+fn source_columns_mut(dv_schema: &mut DVSchema) -> Vec<&mut ColumnData> {
+    let mut source_columns: Vec<&mut ColumnData> = Vec::new();
+
+    for business_key in &mut dv_schema.business_keys {
+        for part_link in &mut business_key.business_key_part_links {
+            source_columns.extend(part_link.source_columns.iter_mut());
+        }
+        for descriptor in &mut business_key.descriptors {
+            source_columns.extend(descriptor.descriptor_link.source_column.as_mut());
+        }
+    }
+
+    for link in &mut dv_schema.links {
+        for hub_reference in &mut link.hub_references {
+            source_columns.extend(hub_reference.source_columns.iter_mut());
+        }
+        for descriptor in &mut link.descriptors {
+            source_columns.extend(descriptor.descriptor_link.source_column.as_mut());
+        }
+    }
+
+    source_columns
+}
@@ -1,28 +1,379 @@
 use pgrx::prelude::*;
-use uuid::Uuid;
+use pgrx::pg_sys::panic::CaughtError;
+use pgrx::spi::{quote_identifier, quote_qualified_identifier};
 use std::collections::HashMap;
 use chrono::Utc;
+use uuid::Uuid;
 
 use crate::model::queries;
 use crate::utility::guc;
 use crate::model::dv_schema::{
                                 DVSchema, 
+                                DWModel,
+                                Naming,
                                 BusinessKey, 
                                 BusinessKeyPartLink, 
+                                Link,
+                                LinkHubReference,
                                 Descriptor, 
                                 DescriptorLink, 
-                                ColumnData
+                                ColumnData,
+                                dv_schema_id,
+                                business_key_id,
+                                business_key_part_link_id,
+                                link_id,
+                                link_hub_reference_id,
+                                descriptor_id,
+                                descriptor_link_id,
+                                column_data_id
                             };
 
 use super::dv_loader::*;
 
-pub fn build_dv(build_id: &String, dv_objects_query: &str) {
+pub fn build_dv(build_id: &String) {
+
+    let (dv_schema, previous_dv_schema) = get_dv_schema(build_id);
+
+    deploy_dv(build_id, dv_schema, previous_dv_schema);
+}
+
+// Builds dv_schema, evolving the tables of previous_dv_schema, then pushes it to the repo and loads it.
+pub fn deploy_dv(build_id: &String, mut dv_schema: DVSchema, previous_dv_schema: Option<DVSchema>) {
+
+    // Build DV
+    // Push DV Function
+    let mut dv_ddl_sql = String::new();
+
+    for business_key in &dv_schema.business_keys {
+        let previous_business_key = get_previous_business_key(&previous_dv_schema, business_key);
+        let dv_business_key_ddl_sql = build_sql_for_business_key(&dv_schema, previous_business_key, business_key);
+        dv_ddl_sql.push_str(&dv_business_key_ddl_sql);
+    }
+
+    for link in &dv_schema.links {
+        let previous_link = get_previous_link(&previous_dv_schema, link);
+        dv_ddl_sql.push_str(&build_sql_for_link(&dv_schema, previous_link, link));
+    }
+
+    dv_ddl_sql.push_str(&build_sql_indexes(&dv_schema));
+
+    // Build Tables using DDL
+    if !dv_ddl_sql.trim().is_empty() {
+        Spi::connect( |mut client| {
+                _ = client.update(&dv_ddl_sql, None, None);
+                log!("DV Tables Built");
+            }
+        );
+    }
+
+    // Add Target Columns to dv_schema links.
+
+    dv_schema_add_target_columns(&mut dv_schema);
+
+    // Objects merged from a previous build or edited by hand may carry IDs that were not derived.
+    dv_schema.derive_ids();
+
+    dv_schema_push_to_repo(&build_id, &mut dv_schema);
+
+    // ToDo: Remove as this is redundant and for testing purposes.  However, this function will be integral for future data refreshes.
+    match dv_load_schema_from_build_id(&build_id) {
+        Some(schema) => {
+            dv_schema = schema;
+        }
+        None => {
+            panic!("Repo Error")
+        }
+    };
+
+    // dv_loader::dv_data_load(&dv_schema);
+    if let Err(e) = dv_data_loader(&dv_schema).and_then(|_| dv_pit_bridge_refresh(&dv_schema)) {
+        error!("Build ID: {} | Data could not be loaded: {}", build_id, e);
+    }
+}
+
+// A statement generated during a dry run along with the error it raised, if any.
+pub struct PlannedStatement {
+    pub step: String,
+    pub statement: String,
+    pub error: Option<String>,
+}
+
+// Plans a build without changing the DW schema.  The hub/satellite DDL and the load DML are executed
+// inside a subtransaction that is always rolled back, so errors surface without anything being kept.
+pub fn dry_run_dv(build_id: &str, status_code: &str) -> Vec<PlannedStatement> {
+
+    // The build call is recorded inside the subtransaction, so a dry run leaves no trace once rolled back.
+    let (memory_context, resource_owner) = begin_subtransaction();
+
+    crate::build_call(build_id, "DryRun", status_code);
+
+    let (mut dv_schema, previous_dv_schema) = get_dv_schema(build_id);
+
+    let mut planned_statements: Vec<PlannedStatement> = Vec::new();
+
+    let naming = dv_schema.naming.clone();
+    let (business_key_table, link_table): (fn(&Naming, &str) -> String, fn(&Naming, &str) -> String) = match dv_schema.dw_model {
+        DWModel::DataVault => (Naming::hub, Naming::link),
+        DWModel::Kimball => (Naming::dimension, Naming::fact),
+    };
+
+    // DDL
+    for business_key in &dv_schema.business_keys {
+        let previous_business_key = get_previous_business_key(&previous_dv_schema, business_key);
+        let statement = build_sql_for_business_key(&dv_schema, previous_business_key, business_key);
+        if statement.trim().is_empty() {
+            continue; // No changes for this business key.
+        }
+        let error = try_execute(&statement).err();
+        planned_statements.push(PlannedStatement { step: format!("DDL: {}", business_key_table(&naming, &business_key.name)), statement, error });
+    }
+
+    for link in &dv_schema.links {
+        let previous_link = get_previous_link(&previous_dv_schema, link);
+        let statement = build_sql_for_link(&dv_schema, previous_link, link);
+        if statement.trim().is_empty() {
+            continue; // No changes for this link.
+        }
+        let error = try_execute(&statement).err();
+        planned_statements.push(PlannedStatement { step: format!("DDL: {}", link_table(&naming, &link.name)), statement, error });
+    }
+
+    let statement = build_sql_indexes(&dv_schema);
+    if !statement.trim().is_empty() {
+        let error = try_execute(&statement).err();
+        planned_statements.push(PlannedStatement { step: "DDL: Indexes".to_string(), statement, error });
+    }
+
+    dv_schema_add_target_columns(&mut dv_schema);
+
+    // DML - One DVSchema per business key so each load is reported on its own.
+    for business_key in &dv_schema.business_keys {
+        let business_key_dv_schema = DVSchema {
+            id: dv_schema.id,
+            dw_schema: dv_schema.dw_schema.clone(),
+            create_timestamp_gmt: dv_schema.create_timestamp_gmt,
+            modified_timestamp_gmt: dv_schema.modified_timestamp_gmt,
+            business_keys: vec![business_key.clone()],
+            links: Vec::new(),
+            dw_model: dv_schema.dw_model,
+            naming: dv_schema.naming.clone(),
+        };
+        let statement = dv_data_loader_dml(&business_key_dv_schema);
+        let error = try_execute(&statement).err();
+        planned_statements.push(PlannedStatement { step: format!("DML: {}", business_key_table(&naming, &business_key.name)), statement, error });
+    }
+
+    for link in &dv_schema.links {
+        let link_dv_schema = DVSchema {
+            id: dv_schema.id,
+            dw_schema: dv_schema.dw_schema.clone(),
+            create_timestamp_gmt: dv_schema.create_timestamp_gmt,
+            modified_timestamp_gmt: dv_schema.modified_timestamp_gmt,
+            business_keys: Vec::new(),
+            links: vec![link.clone()],
+            dw_model: dv_schema.dw_model,
+            naming: dv_schema.naming.clone(),
+        };
+        let statement = dv_data_loader_dml(&link_dv_schema);
+        let error = try_execute(&statement).err();
+        planned_statements.push(PlannedStatement { step: format!("DML: {}", link_table(&naming, &link.name)), statement, error });
+    }
+
+    rollback_subtransaction(memory_context, resource_owner);
+
+    planned_statements
+}
+
+fn begin_subtransaction() -> (pg_sys::MemoryContext, pg_sys::ResourceOwner) {
+    unsafe {
+        let memory_context = pg_sys::CurrentMemoryContext;
+        let resource_owner = pg_sys::CurrentResourceOwner;
+        pg_sys::BeginInternalSubTransaction(std::ptr::null());
+        (memory_context, resource_owner)
+    }
+}
+
+fn rollback_subtransaction(memory_context: pg_sys::MemoryContext, resource_owner: pg_sys::ResourceOwner) {
+    unsafe {
+        pg_sys::RollbackAndReleaseCurrentSubTransaction();
+        pg_sys::MemoryContextSwitchTo(memory_context);
+        pg_sys::CurrentResourceOwner = resource_owner;
+    }
+}
+
+fn release_subtransaction(memory_context: pg_sys::MemoryContext, resource_owner: pg_sys::ResourceOwner) {
+    unsafe {
+        pg_sys::ReleaseCurrentSubTransaction();
+        pg_sys::MemoryContextSwitchTo(memory_context);
+        pg_sys::CurrentResourceOwner = resource_owner;
+    }
+}
+
+// Executes SQL in its own subtransaction, returning the error message instead of aborting the caller.
+pub fn try_execute(sql: &str) -> Result<(), String> {
+    let (memory_context, resource_owner) = begin_subtransaction();
+
+    let result = PgTryBuilder::new(|| Spi::run(sql).map_err(|e| e.to_string()))
+        .catch_others(|caught_error| {
+            match caught_error {
+                CaughtError::PostgresError(error_report) |
+                CaughtError::ErrorReport(error_report) |
+                CaughtError::RustPanic { ereport: error_report, .. } => Err(error_report.message().to_string()),
+            }
+        })
+        .execute();
+
+    match result {
+        Ok(()) => release_subtransaction(memory_context, resource_owner),
+        Err(_) => rollback_subtransaction(memory_context, resource_owner),
+    }
+
+    result
+}
+
+// Assembles the DVSchema for a build.  When the DW schema has been built before, the latest schema in the
+// repo is returned as well and anything it holds that the build does not is merged into the new schema.
+fn get_dv_schema(build_id: &str) -> (DVSchema, Option<DVSchema>) {
+
+    let dv_objects_hm = get_transformer_objects(build_id);
+
+    let naming = get_naming();
+
+    let (business_keys, mut links) = get_business_keys(&dv_objects_hm, &naming);
+
+    for foreign_key_link in get_links(&dv_objects_hm, &business_keys) {
+        if !links.iter().any(|link| link.name == foreign_key_link.name) {
+            links.push(foreign_key_link);
+        }
+    }
+
+    let dw_schema = guc::get_guc(guc::PgAutoDWGuc::DwSchema).expect("DW SCHEMA GUC is not set.");
+
+    let dw_model = get_dw_model();
+
+    // Get the current time in GMT
+    let now_gmt = Utc::now().naive_utc();
+
+    let mut dv_schema = DVSchema {
+        id: dv_schema_id(&dw_schema, dw_model, &naming),
+        dw_schema,
+        create_timestamp_gmt: now_gmt,
+        modified_timestamp_gmt: now_gmt,
+        business_keys,
+        links,
+        dw_model,
+        naming,
+    };
+
+    let previous_dv_schema = get_previous_dv_schema(&dv_schema);
+
+    if let Some(previous_dv_schema) = &previous_dv_schema {
+        dv_schema_merge_previous(&mut dv_schema, previous_dv_schema);
+    }
+
+    (dv_schema, previous_dv_schema)
+}
+
+// The latest build of dv_schema's DW schema, whose tables dv_schema evolves.  Tables built in another model, or
+// named otherwise, would be left behind by the build, so the DW schema must be rolled back before switching.
+pub fn get_previous_dv_schema(dv_schema: &DVSchema) -> Option<DVSchema> {
+    let previous_dv_schema = dv_load_latest_schema(&dv_schema.dw_schema).map(|(_build_id, previous_dv_schema)| previous_dv_schema);
+
+    if let Some(previous_dv_schema) = &previous_dv_schema {
+        if previous_dv_schema.dw_model != dv_schema.dw_model || previous_dv_schema.naming != dv_schema.naming {
+            error!("DW schema \"{}\" was built with another DW model or naming.  Roll back its builds, or build into another DW schema.", dv_schema.dw_schema);
+        }
+    }
+
+    previous_dv_schema
+}
+
+fn get_dw_model() -> DWModel {
+    let dw_model = guc::get_guc(guc::PgAutoDWGuc::DwModel).unwrap_or_else(|| "data_vault".to_string());
+    match dw_model.to_lowercase().as_str() {
+        "data_vault" => DWModel::DataVault,
+        "kimball" => DWModel::Kimball,
+        _ => error!("DW model \"{}\" is not supported.  Available models include, data_vault and kimball.", dw_model),
+    }
+}
+
+// Naming templates from pg_auto_dw.naming_template, a JSON object overriding any of the default templates.
+fn get_naming() -> Naming {
+    let mut naming = Naming::default();
+
+    let naming_template = match guc::get_guc(guc::PgAutoDWGuc::NamingTemplate) {
+        Some(naming_template) if !naming_template.trim().is_empty() => naming_template,
+        _ => return naming,
+    };
+
+    let naming_template: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&naming_template)
+        .unwrap_or_else(|e| error!("Naming template must be a JSON object: {}", e));
+
+    for (key, value) in naming_template {
+        if key == "snake_case_aliases" {
+            naming.snake_case_aliases = value.as_bool()
+                .unwrap_or_else(|| error!("Naming template key \"snake_case_aliases\" must be true or false."));
+            continue;
+        }
+
+        let template = match key.as_str() {
+            "hub" => &mut naming.hub,
+            "link" => &mut naming.link,
+            "satellite" => &mut naming.satellite,
+            "sensitive" => &mut naming.sensitive,
+            "pit" => &mut naming.pit,
+            "bridge" => &mut naming.bridge,
+            "current_view" => &mut naming.current_view,
+            "dimension" => &mut naming.dimension,
+            "fact" => &mut naming.fact,
+            "hash_key" => &mut naming.hash_key,
+            "hash_diff" => &mut naming.hash_diff,
+            "business_key" => &mut naming.business_key,
+            "surrogate_key" => &mut naming.surrogate_key,
+            _ => error!("Naming template key \"{}\" is not supported.  Available keys include, hub, link, satellite, sensitive, pit, bridge, current_view, dimension, fact, hash_key, hash_diff, business_key, surrogate_key, and snake_case_aliases.", key),
+        };
+
+        match value.as_str() {
+            Some(value) if value.contains("{name}") || value.contains("{table}") => *template = value.to_string(),
+            _ => error!("Naming template key \"{}\" must be text containing {{name}} or {{table}}, received {}.", key, value),
+        }
+    }
+
+    naming
+}
+
+// DDL for a business key in the model of dv_schema, either a hub with its satellites or SCD2 dimensions.
+pub fn build_sql_for_business_key(dv_schema: &DVSchema, previous_business_key: Option<&BusinessKey>, business_key: &BusinessKey) -> String {
+    match dv_schema.dw_model {
+        DWModel::DataVault => build_sql_from_business_key_changes(dv_schema, previous_business_key, business_key),
+        DWModel::Kimball => build_sql_dimensions(dv_schema, business_key),
+    }
+}
+
+// DDL for a link in the model of dv_schema, either a link with its satellites or a fact table.
+pub fn build_sql_for_link(dv_schema: &DVSchema, previous_link: Option<&Link>, link: &Link) -> String {
+    match dv_schema.dw_model {
+        DWModel::DataVault => build_sql_from_link_changes(dv_schema, previous_link, link),
+        DWModel::Kimball => build_sql_fact(dv_schema, link),
+    }
+}
+
+fn get_previous_business_key<'a>(previous_dv_schema: &'a Option<DVSchema>, business_key: &BusinessKey) -> Option<&'a BusinessKey> {
+    previous_dv_schema
+        .as_ref()
+        .and_then(|previous_dv_schema| previous_dv_schema.business_keys.iter().find(|previous_business_key| previous_business_key.name == business_key.name))
+}
+
+// Source columns recorded against the build by build_call, grouped by table OID.
+fn get_transformer_objects(build_id: &str) -> HashMap<u32, Vec<TransformerObject>> {
 
     let mut dv_objects_hm: HashMap<u32, Vec<TransformerObject>> = HashMap::new();
 
     Spi::connect(|client| 
         {
-            let dv_objects_result = client.select(dv_objects_query, None, None);
+            let dv_objects_result = client.select(queries::BUILD_OBJECT_PULL, None, Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), build_id.into_datum()),
+            ]));
 
             match dv_objects_result {
 
@@ -39,6 +390,7 @@ pub fn build_dv(build_id: &String, dv_objects_query: &str) {
                         let system_id = dv_object.get_datum_by_ordinal(7).unwrap().value::<i64>().unwrap().unwrap();
                         let table_oid: u32 = dv_object.get_datum_by_ordinal(8).unwrap().value::<u32>().unwrap().unwrap();
                         let column_ordinal_position = dv_object.get_datum_by_ordinal(9).unwrap().value::<i16>().unwrap().unwrap();
+                        let alias = dv_object.get_datum_by_ordinal(10).unwrap().value::<String>().unwrap();
                         
                         let column_category = ColumnCategory::from_str(&column_category);
 
@@ -53,6 +405,7 @@ pub fn build_dv(build_id: &String, dv_objects_query: &str) {
                                 table_oid, 
                                 column_ordinal_position, 
                                 column_category, 
+                                alias,
                             };
 
                         // Bucket TransformerObject by table
@@ -71,136 +424,303 @@ pub fn build_dv(build_id: &String, dv_objects_query: &str) {
         }
     );
 
+    dv_objects_hm
+}
+
+fn get_previous_link<'a>(previous_dv_schema: &'a Option<DVSchema>, link: &Link) -> Option<&'a Link> {
+    previous_dv_schema
+        .as_ref()
+        .and_then(|previous_dv_schema| previous_dv_schema.links.iter().find(|previous_link| previous_link.name == link.name))
+}
+
+// Builds a BusinessKey for each distinct business key name in a table.  A table holding more than one
+// business key is represented by a link across its hubs, and its descriptors are attached to that link.
+// Business keys of the same name in several tables are merged into one hub sourced from each of them.
+fn get_business_keys(dv_objects_hm: &HashMap<u32, Vec<TransformerObject>>, naming: &Naming) -> (Vec<BusinessKey>, Vec<Link>) {
+
     // Build a Vector of BusinessKey's
     let mut business_keys: Vec<BusinessKey> = Vec::new();
-    for dv_objects_v in dv_objects_hm {
+    let mut links: Vec<Link> = Vec::new();
+
+    // Tables in OID order, so merged business keys list their source tables in the same order every build.
+    let mut dv_objects_by_table: Vec<(&u32, &Vec<TransformerObject>)> = dv_objects_hm.iter().collect();
+    dv_objects_by_table.sort_by_key(|(table_oid, _)| **table_oid);
+
+    let status_tracking = guc::get_guc(guc::PgAutoDWGuc::StatusTracking).map_or(false, |value| value == "true");
+    for dv_objects_v in dv_objects_by_table {
 
         let mut descriptors: Vec<Descriptor> = Vec::new();
-        let mut business_key_part_links: Vec<BusinessKeyPartLink> = Vec::new();
+        let mut business_key_part_links_by_name: Vec<(String, Vec<BusinessKeyPartLink>)> = Vec::new();
 
         // Build Descriptors
-        for dv_object in &dv_objects_v.1 {
-
-            let column_data_id = Uuid::new_v4();
-
-            let column_data = ColumnData {
-                id: column_data_id,
-                system_id: dv_object.system_id,
-                schema_name: dv_object.schema_name.clone(),
-                table_oid: dv_object.table_oid,
-                table_name: dv_object.table_name.clone(),
-                column_name: dv_object.column_name.clone(),
-                column_ordinal_position: dv_object.column_ordinal_position,
-                column_type_name: dv_object.column_type_name.clone(),
-            };
-            let orbit = dv_object.table_name.clone();
+        for dv_object in dv_objects_v.1 {
+
+            let column_data = get_source_column_data(dv_object);
+            let orbit = naming.alias(&dv_object.table_name);
+            let alias = dv_object.alias.clone().unwrap_or_else(|| naming.alias(&dv_object.column_name));
 
             if dv_object.column_category == ColumnCategory::Descriptor {
-                let descriptor = get_descriptor(dv_object.column_name.clone(), column_data, orbit, false);
+                let descriptor = get_descriptor(alias, column_data, orbit, false);
                 descriptors.push(descriptor);
             } else if dv_object.column_category == ColumnCategory::DescriptorSensitive {
-                let descriptor = get_descriptor(dv_object.column_name.clone(), column_data, orbit, true);
+                let descriptor = get_descriptor(alias, column_data, orbit, true);
                 descriptors.push(descriptor);
             }
         }
 
-        // Build Business Key Part Links
-        for dv_object in &dv_objects_v.1 {
-
-            let column_data_id = Uuid::new_v4();
-
-            let column_data = ColumnData {
-                id: column_data_id,
-                system_id: dv_object.system_id,
-                schema_name: dv_object.schema_name.clone(),
-                table_oid: dv_object.table_oid,
-                table_name: dv_object.table_name.clone(),
-                column_name: dv_object.column_name.clone(),
-                column_ordinal_position: dv_object.column_ordinal_position,
-                column_type_name: dv_object.column_type_name.clone(),
-            };
+        // Build Business Key Part Links, grouped by Business Key Name
+        for dv_object in dv_objects_v.1 {
 
             if dv_object.column_category == ColumnCategory::BusinessKeyPart {
-                let business_key_part_link = get_business_key_part_link(dv_object.column_name.clone(), column_data);
-                business_key_part_links.push(business_key_part_link);
+                let column_data = get_source_column_data(dv_object);
+                let business_key_name = dv_object.business_key_name.clone();
+                let business_key_part_link = get_business_key_part_link(&business_key_name, naming.alias(&dv_object.column_name), column_data);
+
+                match business_key_part_links_by_name.iter_mut().find(|(name, _)| *name == business_key_name) {
+                    Some((_, business_key_part_links)) => business_key_part_links.push(business_key_part_link),
+                    None => business_key_part_links_by_name.push((business_key_name, vec![business_key_part_link])),
+                }
             }
         }
 
-        // TODO: Handle multiple business keys for link tables. Ensure appropriate error handling!
-        let business_key_name: String = {
-            let mut business_key_name = String::new();
-            for dv_object in &dv_objects_v.1 {
-                if dv_object.business_key_name.to_lowercase() != "na" {
-                    business_key_name = dv_object.business_key_name.to_lowercase().clone();
+        let table_business_keys: Vec<BusinessKey> = business_key_part_links_by_name
+            .into_iter()
+            .map(|(business_key_name, business_key_part_links)| BusinessKey {
+                id: business_key_id(&business_key_name),
+                name: business_key_name,
+                business_key_part_links,
+                descriptors: Vec::new(),
+                status_tracking,
+            })
+            .collect();
+
+        match table_business_keys.len() {
+            0 => {
+                if let Some(dv_object) = dv_objects_v.1.first() {
+                    log!("Table {}.{} skipped, no business key parts found.", dv_object.schema_name, dv_object.table_name);
                 }
             }
-            business_key_name
-        };
+            1 => {
+                let mut business_key = table_business_keys.into_iter().next().unwrap();
+                business_key.descriptors = descriptors;
+                merge_business_key(&mut business_keys, business_key);
+            }
+            _ => {
+                let link_name = table_business_keys
+                    .iter()
+                    .map(|business_key| business_key.name.clone())
+                    .collect::<Vec<String>>()
+                    .join("_");
+
+                let hub_references: Vec<LinkHubReference> = table_business_keys
+                    .iter()
+                    .map(|business_key| LinkHubReference {
+                        id: link_hub_reference_id(&link_name, &business_key.name),
+                        business_key_name: business_key.name.clone(),
+                        source_columns: business_key.business_key_part_links
+                            .iter()
+                            .map(|part_link| part_link.source_columns[0].clone())
+                            .collect(),
+                        target_column: None,
+                    })
+                    .collect();
+
+                links.push(Link {
+                    id: link_id(&link_name),
+                    name: link_name,
+                    hub_references,
+                    descriptors,
+                });
+
+                for business_key in table_business_keys {
+                    merge_business_key(&mut business_keys, business_key);
+                }
+            }
+        }
+    }
 
-        let business_key_id = Uuid::new_v4();
-        let business_key = BusinessKey {
-            id: business_key_id,
-            name: business_key_name,
-            business_key_part_links,
-            descriptors 
-        };
+    (business_keys, links)
+}
 
-        business_keys.push(business_key);
-    }
+// Adds a table's business key to business_keys.  When a business key of the same name was found in an earlier table,
+// each part link gains the table's column for that part, in part order, and the table's descriptors are added.
+fn merge_business_key(business_keys: &mut Vec<BusinessKey>, business_key: BusinessKey) {
 
-    let dw_schema = guc::get_guc(guc::PgAutoDWGuc::DwSchema).expect("DW SCHEMA GUC is not set.");
+    let existing_business_key = match business_keys.iter_mut().find(|existing_business_key| existing_business_key.name == business_key.name) {
+        Some(existing_business_key) => existing_business_key,
+        None => {
+            business_keys.push(business_key);
+            return;
+        }
+    };
 
-    // Build DV
-    // Push DV Function
-    let mut dv_ddl_sql = String::new();
+    if existing_business_key.business_key_part_links.len() != business_key.business_key_part_links.len() {
+        if let Some(source_column) = business_key.business_key_part_links.first().and_then(|part_link| part_link.source_columns.first()) {
+            warning!("Business key {} of {}.{} skipped, it has {} part(s) where other tables have {}.", 
+                business_key.name, source_column.schema_name, source_column.table_name, 
+                business_key.business_key_part_links.len(), existing_business_key.business_key_part_links.len());
+        }
+        return;
+    }
 
-    for business_key in &business_keys {
-        let dv_business_key_ddl_sql = build_sql_from_business_key(&dw_schema, business_key);
-        dv_ddl_sql.push_str(&dv_business_key_ddl_sql);
+    for (existing_part_link, part_link) in existing_business_key.business_key_part_links.iter_mut().zip(business_key.business_key_part_links) {
+        existing_part_link.source_columns.extend(part_link.source_columns);
     }
 
-    // Build Tables using DDL
-    Spi::connect( |mut client| {
-            _ = client.update(&dv_ddl_sql, None, None);
-            log!("DV Tables Built");
+    // Descriptor aliases name the columns of current views and dimensions, so an alias already in use is prefixed with its orbit.
+    for mut descriptor in business_key.descriptors {
+        if existing_business_key.descriptors.iter().any(|existing_descriptor| existing_descriptor.descriptor_link.alias == descriptor.descriptor_link.alias) {
+            descriptor.descriptor_link.alias = format!("{}_{}", descriptor.orbit, descriptor.descriptor_link.alias);
+            descriptor.id = descriptor_id(descriptor.descriptor_link.source_column.as_ref(), &descriptor.descriptor_link.alias);
+            descriptor.descriptor_link.id = descriptor_link_id(&descriptor.id);
         }
-    );
+        existing_business_key.descriptors.push(descriptor);
+    }
+}
 
+// Links are derived from foreign keys.  A foreign key from a table with a hub to the business key part
+// columns of another hub links the two hubs, using the foreign key columns to hash the referenced hub key.
+fn get_links(dv_objects_hm: &HashMap<u32, Vec<TransformerObject>>, business_keys: &Vec<BusinessKey>) -> Vec<Link> {
+
+    // (table_oid, referenced_table_oid, [(column_ordinal_position, referenced_column_ordinal_position)]) by constraint
+    let mut foreign_keys: Vec<(u32, u32, u32, Vec<(i16, i16)>)> = Vec::new();
+
+    Spi::connect(|client| {
+        match client.select(queries::FOREIGN_KEY_COLUMNS, None, None) {
+            Ok(foreign_key_columns) => {
+                for foreign_key_column in foreign_key_columns {
+                    let constraint_oid = foreign_key_column.get_datum_by_ordinal(1).unwrap().value::<u32>().unwrap().unwrap();
+                    let table_oid = foreign_key_column.get_datum_by_ordinal(2).unwrap().value::<u32>().unwrap().unwrap();
+                    let referenced_table_oid = foreign_key_column.get_datum_by_ordinal(3).unwrap().value::<u32>().unwrap().unwrap();
+                    let column_ordinal_position = foreign_key_column.get_datum_by_ordinal(4).unwrap().value::<i16>().unwrap().unwrap();
+                    let referenced_column_ordinal_position = foreign_key_column.get_datum_by_ordinal(5).unwrap().value::<i16>().unwrap().unwrap();
+
+                    match foreign_keys.last_mut() {
+                        Some(foreign_key) if foreign_key.0 == constraint_oid => {
+                            foreign_key.3.push((column_ordinal_position, referenced_column_ordinal_position));
+                        }
+                        _ => {
+                            foreign_keys.push((constraint_oid, table_oid, referenced_table_oid, vec![(column_ordinal_position, referenced_column_ordinal_position)]));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                log!("Error getting Foreign Key Columns Result: {:?}", e);
+            }
+        }
+    });
 
-    // Build DVTransformerSchema
+    let mut links: Vec<Link> = Vec::new();
 
-    // Get the current time in GMT
-    let now_gmt = Utc::now().naive_utc();
+    for (_constraint_oid, table_oid, referenced_table_oid, column_pairs) in foreign_keys {
 
-    let mut dv_schema = DVSchema {
-        id: Uuid::new_v4(),
-        dw_schema,
-        create_timestamp_gmt: now_gmt,
-        modified_timestamp_gmt: now_gmt,
-        business_keys,
-    };
+        let dv_objects = match dv_objects_hm.get(&table_oid) {
+            Some(dv_objects) => dv_objects,
+            None => continue, // Table is not part of this build.
+        };
 
-    // Add Target Columns to dv_schema links.
+        // Hubs that could be sourced from the referencing table.
+        let source_business_keys = business_keys
+            .iter()
+            .filter(|business_key| {
+                !business_key.business_key_part_links.is_empty() &&
+                business_key.business_key_part_links.iter().all(|part_link| part_link.source_columns.iter().any(|source_column| source_column.table_oid == table_oid))
+            });
 
-    dv_schema_add_target_columns(&mut dv_schema);
+        // Hub whose business key parts, as sourced from the referenced table, are all covered by the foreign key.
+        let referenced_column_position = |part_link: &BusinessKeyPartLink| -> Option<i16> {
+            part_link.source_columns
+                .iter()
+                .filter(|source_column| source_column.table_oid == referenced_table_oid)
+                .map(|source_column| source_column.column_ordinal_position)
+                .find(|column_ordinal_position| column_pairs.iter().any(|(_, referenced_column)| referenced_column == column_ordinal_position))
+        };
 
-    dv_schema_push_to_repo(&build_id, &mut dv_schema);
+        let referenced_business_key = business_keys
+            .iter()
+            .find(|business_key| {
+                !business_key.business_key_part_links.is_empty() &&
+                business_key.business_key_part_links.iter().all(|part_link| referenced_column_position(part_link).is_some())
+            });
 
-    // ToDo: Remove as this is redundant and for testing purposes.  However, this function will be integral for future data refreshes.
-    match dv_load_schema_from_build_id(&build_id) {
-        Some(schema) => {
-            dv_schema = schema;
-        }
-        None => {
-            panic!("Repo Error")
+        let referenced_business_key = match referenced_business_key {
+            Some(referenced_business_key) => referenced_business_key,
+            None => continue, // Referenced table has no hub in this build.
+        };
+
+        // Foreign key columns ordered by the referenced hub's business key parts, so both hash alike.
+        let referenced_source_columns: Option<Vec<ColumnData>> = referenced_business_key.business_key_part_links
+            .iter()
+            .map(|part_link| {
+                let referenced_column = referenced_column_position(part_link)?;
+                column_pairs
+                    .iter()
+                    .find(|(_, column_pair_referenced_column)| *column_pair_referenced_column == referenced_column)
+                    .and_then(|(column, _)| dv_objects.iter().find(|dv_object| dv_object.column_ordinal_position == *column))
+                    .map(get_source_column_data)
+            })
+            .collect();
+
+        let referenced_source_columns = match referenced_source_columns {
+            Some(referenced_source_columns) => referenced_source_columns,
+            None => continue, // Foreign key columns are not part of this build.
+        };
+
+        for source_business_key in source_business_keys {
+
+            if source_business_key.name == referenced_business_key.name {
+                log!("Link skipped, business key {} references itself.", source_business_key.name);
+                continue;
+            }
+
+            let link_name = format!("{}_{}", source_business_key.name, referenced_business_key.name);
+
+            if links.iter().any(|link| link.name == link_name) {
+                continue;
+            }
+
+            let source_hub_reference = LinkHubReference {
+                id: link_hub_reference_id(&link_name, &source_business_key.name),
+                business_key_name: source_business_key.name.clone(),
+                source_columns: source_business_key.business_key_part_links
+                    .iter()
+                    .filter_map(|part_link| part_link.source_columns.iter().find(|source_column| source_column.table_oid == table_oid).cloned())
+                    .collect(),
+                target_column: None,
+            };
+
+            let referenced_hub_reference = LinkHubReference {
+                id: link_hub_reference_id(&link_name, &referenced_business_key.name),
+                business_key_name: referenced_business_key.name.clone(),
+                source_columns: referenced_source_columns.clone(),
+                target_column: None,
+            };
+
+            links.push(Link {
+                id: link_id(&link_name),
+                name: link_name,
+                hub_references: vec![source_hub_reference, referenced_hub_reference],
+                descriptors: Vec::new(),
+            });
         }
-    };
+    }
 
-    // dv_loader::dv_data_load(&dv_schema);
-    dv_data_loader(&dv_schema);
+    links
 }
 
-
+fn get_source_column_data(dv_object: &TransformerObject) -> ColumnData {
+    ColumnData {
+        id: column_data_id(dv_object.system_id, dv_object.table_oid, dv_object.column_ordinal_position),
+        system_id: dv_object.system_id,
+        schema_name: dv_object.schema_name.clone(),
+        table_oid: dv_object.table_oid,
+        table_name: dv_object.table_name.clone(),
+        column_name: dv_object.column_name.clone(),
+        column_ordinal_position: dv_object.column_ordinal_position,
+        column_type_name: dv_object.column_type_name.clone(),
+    }
+}
 
 fn dv_schema_push_to_repo(build_id: &String, dv_schema: &mut DVSchema) {
 
@@ -229,57 +749,17 @@ fn dv_schema_push_to_repo(build_id: &String, dv_schema: &mut DVSchema) {
 
 fn dv_schema_add_target_columns(dv_schema: &mut DVSchema) {
 
+    let naming = &dv_schema.naming;
+
     for business_key in &mut dv_schema.business_keys {
 
         // For Descriptors in Business Keys
         for descriptor in &mut business_key.descriptors {
             let schema_name = &dv_schema.dw_schema;
-            let table_name = &{"sat_".to_string() + &descriptor.orbit + {if descriptor.is_sensitive { "_sensitive" } else {""}}};
+            let table_name = &naming.descriptor_satellite(descriptor);
             let column_name = &descriptor.descriptor_link.alias;
-            
-            let get_column_data = queries::get_column_data(schema_name, table_name, column_name);
-
-            let column_data: Option<ColumnData> = Spi::connect( |client| {
-
-                match client.select(&get_column_data, None, None) {
-                    Ok(column_data) => {
-                        // Only 0 or 1 record should be returned.
-                        if let Some(column_data_record) = column_data.into_iter().next() {
-                            let system_id =  column_data_record.get_datum_by_ordinal(1).unwrap().value::<i64>().unwrap().unwrap();
-                            let _schema_oid =  column_data_record.get_datum_by_ordinal(2).unwrap().value::<u32>().unwrap().unwrap();
-                            let schema_name =  column_data_record.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap().unwrap();
-                            let table_name =  column_data_record.get_datum_by_ordinal(4).unwrap().value::<String>().unwrap().unwrap();
-                            let table_oid =  column_data_record.get_datum_by_ordinal(5).unwrap().value::<u32>().unwrap().unwrap();
-                            let column_name =  column_data_record.get_datum_by_ordinal(6).unwrap().value::<String>().unwrap().unwrap();
-                            let column_ordinal_position =  column_data_record.get_datum_by_ordinal(7).unwrap().value::<i16>().unwrap().unwrap(); 
-                            let column_type_name =  column_data_record.get_datum_by_ordinal(8).unwrap().value::<String>().unwrap().unwrap();
-
-                            let column_id = Uuid::new_v4();
-
-                            let column_data = ColumnData {
-                                id: column_id,
-                                system_id,
-                                schema_name,
-                                table_oid,
-                                table_name,
-                                column_name,
-                                column_ordinal_position,
-                                column_type_name,
-                            };
 
-                            return Some(column_data)
-                            
-                        } else {
-                            log!("Column Data Not available.");
-                        }
-                        return None
-                    }
-                    Err(e) => {
-                        log!("Target Column Data Error: {:?}", e);
-                        return None
-                    }
-                }
-            });
+            let column_data: Option<ColumnData> = get_column_data(schema_name, table_name, column_name);
 
             descriptor.descriptor_link.target_column = column_data;
         }
@@ -287,69 +767,87 @@ fn dv_schema_add_target_columns(dv_schema: &mut DVSchema) {
         // For Business Key Parts in Business Keys
         for business_key_part_link in &mut business_key.business_key_part_links {
             let schema_name = &dv_schema.dw_schema;
-            let table_name = &{"hub_".to_string() + &business_key.name};
-            let column_name = &(business_key_part_link.alias.clone() + "_bk");
-
-            let get_column_data= queries::get_column_data(schema_name, table_name, column_name);
-
-            let column_data: Option<ColumnData> = Spi::connect( |client| {
-
-                match client.select(&get_column_data, None, None) {
-                    Ok(column_data) => {
-                        // Only 0 or 1 record should be returned.
-                        if let Some(column_data_record) = column_data.into_iter().next() {
-                            let system_id =  column_data_record.get_datum_by_ordinal(1).unwrap().value::<i64>().unwrap().unwrap();
-                            let _schema_oid =  column_data_record.get_datum_by_ordinal(2).unwrap().value::<u32>().unwrap().unwrap();
-                            let schema_name =  column_data_record.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap().unwrap();
-                            let table_name =  column_data_record.get_datum_by_ordinal(4).unwrap().value::<String>().unwrap().unwrap();
-                            let table_oid =  column_data_record.get_datum_by_ordinal(5).unwrap().value::<u32>().unwrap().unwrap();
-                            let column_name =  column_data_record.get_datum_by_ordinal(6).unwrap().value::<String>().unwrap().unwrap();
-                            let column_ordinal_position =  column_data_record.get_datum_by_ordinal(7).unwrap().value::<i16>().unwrap().unwrap(); 
-                            let column_type_name =  column_data_record.get_datum_by_ordinal(8).unwrap().value::<String>().unwrap().unwrap();
-
-                            let column_id = Uuid::new_v4();
-
-                            let column_data = ColumnData {
-                                id: column_id,
-                                system_id,
-                                schema_name,
-                                table_oid,
-                                table_name,
-                                column_name,
-                                column_ordinal_position,
-                                column_type_name,
-                            };
+            let table_name = &naming.hub(&business_key.name);
+            let column_name = &naming.business_key(&business_key_part_link.alias);
 
-                            return Some(column_data)
-                            
-                        } else {
-                            log!("Column Data Not available.");
-                            
-                        }
-                        return None
-                    }
-                    Err(e) => {
-                        log!("Target Column Data Error: {:?}", e);
-                        return None
-                    }
-                }
-            });
+            let column_data: Option<ColumnData> = get_column_data(schema_name, table_name, column_name);
 
             business_key_part_link.target_column = column_data;
         }
         
     }
+
+    // For Hub References and Descriptors in Links
+    for link in &mut dv_schema.links {
+        for descriptor in &mut link.descriptors {
+            let schema_name = &dv_schema.dw_schema;
+            let table_name = &naming.descriptor_satellite(descriptor);
+            let column_name = &descriptor.descriptor_link.alias;
+
+            descriptor.descriptor_link.target_column = get_column_data(schema_name, table_name, column_name);
+        }
+
+        for hub_reference in &mut link.hub_references {
+            let schema_name = &dv_schema.dw_schema;
+            let table_name = &naming.link(&link.name);
+            let column_name = &naming.hub_hash_key(&hub_reference.business_key_name);
+
+            hub_reference.target_column = get_column_data(schema_name, table_name, column_name);
+        }
+    }
 }
 
-fn get_descriptor(column_name: String, column_data: ColumnData, orbit: String, is_sensitive: bool) -> Descriptor {
-    let descriptor_link_id = Uuid::new_v4();
+// Column data for a table column from the catalog, None when the column is not available.
+pub fn get_column_data(schema_name: &str, table_name: &str, column_name: &str) -> Option<ColumnData> {
+    Spi::connect( |client| {
+
+        match client.select(queries::GET_COLUMN_DATA, None, Some(vec![
+            (PgOid::from(pg_sys::TEXTOID), schema_name.into_datum()),
+            (PgOid::from(pg_sys::TEXTOID), table_name.into_datum()),
+            (PgOid::from(pg_sys::TEXTOID), column_name.into_datum()),
+        ])) {
+            Ok(column_data) => {
+                // Only 0 or 1 record should be returned.
+                if let Some(column_data_record) = column_data.into_iter().next() {
+                    let system_id =  column_data_record.get_datum_by_ordinal(1).unwrap().value::<i64>().unwrap().unwrap();
+                    let schema_name =  column_data_record.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap().unwrap();
+                    let table_name =  column_data_record.get_datum_by_ordinal(4).unwrap().value::<String>().unwrap().unwrap();
+                    let table_oid =  column_data_record.get_datum_by_ordinal(5).unwrap().value::<u32>().unwrap().unwrap();
+                    let column_name =  column_data_record.get_datum_by_ordinal(6).unwrap().value::<String>().unwrap().unwrap();
+                    let column_ordinal_position =  column_data_record.get_datum_by_ordinal(7).unwrap().value::<i16>().unwrap().unwrap(); 
+                    let column_type_name =  column_data_record.get_datum_by_ordinal(8).unwrap().value::<String>().unwrap().unwrap();
+
+                    return Some(ColumnData {
+                        id: column_data_id(system_id, table_oid, column_ordinal_position),
+                        system_id,
+                        schema_name,
+                        table_oid,
+                        table_name,
+                        column_name,
+                        column_ordinal_position,
+                        column_type_name,
+                    })
+                } else {
+                    log!("Column Data Not available.");
+                }
+                return None
+            }
+            Err(e) => {
+                log!("Target Column Data Error: {:?}", e);
+                return None
+            }
+        }
+    })
+}
+
+fn get_descriptor(alias: String, column_data: ColumnData, orbit: String, is_sensitive: bool) -> Descriptor {
+    let descriptor_id = descriptor_id(Some(&column_data), &alias);
     let descriptor_link = DescriptorLink {
-        id: descriptor_link_id,
-        alias: column_name, // TODO: Give the user an option to change name in the future - modality TBD.
+        id: descriptor_link_id(&descriptor_id),
+        alias,
         source_column: Some(column_data),
         target_column: None,
     };
-    let descriptor_id = Uuid::new_v4();
     let descriptor = Descriptor {
         id: descriptor_id,
         descriptor_link,
@@ -360,8 +858,8 @@ fn get_descriptor(column_name: String, column_data: ColumnData, orbit: String, i
     descriptor
 }
 
-fn get_business_key_part_link(alias: String, column_data: ColumnData) -> BusinessKeyPartLink {
-    let business_key_part_link_id = Uuid::new_v4();
+fn get_business_key_part_link(business_key_name: &str, alias: String, column_data: ColumnData) -> BusinessKeyPartLink {
+    let business_key_part_link_id = business_key_part_link_id(business_key_name, &alias);
     let mut sources_column_data: Vec<ColumnData> = Vec::new(); 
     sources_column_data.push(column_data);
 
@@ -375,7 +873,10 @@ fn get_business_key_part_link(alias: String, column_data: ColumnData) -> Busines
     business_key_link
 }
 
-fn build_sql_from_business_key(dw_schema: &String, business_key: &BusinessKey) -> String {
+fn build_sql_from_business_key(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let dw_schema = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
     let mut dv_business_key_ddl_sql = String::new();
 
     // Hub Buildout
@@ -383,70 +884,516 @@ fn build_sql_from_business_key(dw_schema: &String, business_key: &BusinessKey) -
 
     for part_link in &business_key.business_key_part_links {
         let r = format!(r#",
-            {}_bk VARCHAR"#, part_link.alias);
+            {} VARCHAR"#, quote_identifier(naming.business_key(&part_link.alias)));
         hub_bks.push_str(&r);
     }
 
     let hub_sql = 
     format!(r#"
-        CREATE TABLE {}.hub_{} (
-            hub_{}_hk VARCHAR NOT NULL,
+        CREATE TABLE {} (
+            {} VARCHAR NOT NULL,
             load_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL,
             record_source VARCHAR NOT NULL{}
         );
-    "#, dw_schema, business_key.name, business_key.name, hub_bks);
+    "#, quote_qualified_identifier(dw_schema, &naming.hub(&business_key.name)), quote_identifier(naming.hub_hash_key(&business_key.name)), hub_bks);
 
     dv_business_key_ddl_sql.push_str(&format!(
         r#"
         {}"#, hub_sql));
 
     // Sat Buildout
-    let mut satellite_sqls: HashMap<String, String> = HashMap::new(); 
+    let parent_hash_key = naming.hub_hash_key(&business_key.name);
+    dv_business_key_ddl_sql.push_str(&build_sql_satellites(dv_schema, &parent_hash_key, &business_key.descriptors));
+
+    // Status Sat Buildout
+    if business_key.status_tracking {
+        dv_business_key_ddl_sql.push_str(&build_sql_status_satellite(dv_schema, business_key));
+    }
 
-    for descriptor in &business_key.descriptors {
+    // PIT Buildout
+    dv_business_key_ddl_sql.push_str(&build_sql_pit(dv_schema, business_key));
 
-        let sensitive_string = {
-            if descriptor.is_sensitive == true {
-                "_sensitive".to_string()
-            } else {
-                "".to_string()
-            }
-        };
+    // Current View Buildout
+    dv_business_key_ddl_sql.push_str(&build_sql_current_views(dv_schema, business_key));
+
+    dv_business_key_ddl_sql
+}
+
+// PIT table holding, per hub key and snapshot, the latest load_ts of each of the hub's satellites.
+// Statements are idempotent so satellites added on later builds are picked up as new columns.
+fn build_sql_pit(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let naming = &dv_schema.naming;
+    let pit_table = quote_qualified_identifier(&dv_schema.dw_schema, &naming.pit(&business_key.name));
+
+    let mut pit_sql = format!(r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            {} VARCHAR NOT NULL,
+            snapshot_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL
+        );
+    "#, pit_table, quote_identifier(naming.hub_hash_key(&business_key.name)));
+
+    for satellite_name in dv_pit_satellite_names(naming, business_key) {
+        pit_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} TIMESTAMP WITHOUT TIME ZONE;
+    "#, pit_table, quote_identifier(dv_pit_load_ts_column(&satellite_name))));
+    }
+
+    pit_sql
+}
+
+// Status satellite recording when a hub's business keys are deleted from, or reappear in, the source.
+fn build_sql_status_satellite(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let naming = &dv_schema.naming;
+    format!(r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            {} VARCHAR NOT NULL,
+            load_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+            record_source VARCHAR NOT NULL,
+            status VARCHAR NOT NULL
+        );
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &naming.status_satellite(&business_key.name)), quote_identifier(naming.hub_hash_key(&business_key.name)))
+}
+
+// Satellite DDL for the descriptors of a hub or link, parent_hash_key being the hub or link hash key column.
+fn build_sql_satellites(dv_schema: &DVSchema, parent_hash_key: &String, descriptors: &Vec<Descriptor>) -> String {
+    let naming = &dv_schema.naming;
+
+    let mut dv_satellites_ddl_sql = String::new();
+
+    // Satellites in order of their first descriptor, along with the column definitions of their descriptors.
+    let mut satellites: Vec<(&Descriptor, String)> = Vec::new();
+
+    for descriptor in descriptors {
 
-        let satellite_sql_key = descriptor.orbit.clone() + &sensitive_string;
-        let desc_column_name = &descriptor.descriptor_link.alias;
         let desc_column_type = &descriptor.descriptor_link.source_column.as_ref().unwrap().column_type_name;
-        let sat_descriptor_sql_part: String = format!(",\n    {} {}", desc_column_name, desc_column_type);
+        let sat_descriptor_sql_part: String = format!(",\n    {} {}", quote_identifier(&descriptor.descriptor_link.alias), desc_column_type);
 
-        if let Some(existing_sat_sql) = satellite_sqls.get_mut(&satellite_sql_key) {
-            if let Some(pos) = existing_sat_sql.find(");") {
-                existing_sat_sql.insert_str(pos, &sat_descriptor_sql_part);
-            } else {
-                println!("The substring \");\" was not found in the original string.");
-            }
-        } else {
-            let begin_sat_sql = 
-                format!(r#"
-                    CREATE TABLE {}.sat_{} (
-                        hub_{}_hk VARCHAR NOT NULL,
+        match satellites.iter_mut().find(|(satellite_descriptor, _)| get_satellite_key(satellite_descriptor) == get_satellite_key(descriptor)) {
+            Some((_, sat_descriptors_sql)) => sat_descriptors_sql.push_str(&sat_descriptor_sql_part),
+            None => satellites.push((descriptor, sat_descriptor_sql_part)),
+        }
+    }
+
+    for (descriptor, sat_descriptors_sql) in satellites {
+        dv_satellites_ddl_sql.push_str(&format!(r#"
+                    CREATE TABLE {} (
+                        {} VARCHAR NOT NULL,
                         load_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL,
                         record_source VARCHAR NOT NULL,
-                        sat_{}_hd VARCHAR NOT NULL{});
-                "#, dw_schema, satellite_sql_key, business_key.name, satellite_sql_key, sat_descriptor_sql_part);
-            satellite_sqls.insert(satellite_sql_key, begin_sat_sql);
-        }
+                        {} VARCHAR NOT NULL{});
+                "#, 
+                quote_qualified_identifier(&dv_schema.dw_schema, &naming.descriptor_satellite(descriptor)), 
+                quote_identifier(parent_hash_key), 
+                quote_identifier(naming.descriptor_satellite_hash_diff(descriptor)), sat_descriptors_sql));
+    }
+
+    dv_satellites_ddl_sql
+}
+
+// Evolves the tables of a business key that was already built.  Existing tables are never dropped or
+// re-created, new business key parts and descriptors are added as columns and new satellites are created.
+fn build_sql_from_business_key_changes(dv_schema: &DVSchema, previous_business_key: Option<&BusinessKey>, business_key: &BusinessKey) -> String {
+
+    let previous_business_key = match previous_business_key {
+        Some(previous_business_key) => previous_business_key,
+        None => return build_sql_from_business_key(dv_schema, business_key),
+    };
+
+    let naming = &dv_schema.naming;
 
+    let mut dv_business_key_ddl_sql = String::new();
+
+    // Hub Changes
+    // Hub rows are keyed by the hash of their business key parts, so changing the parts would give existing
+    // entities a second hash key.
+    let part_aliases = |business_key: &BusinessKey| business_key.business_key_part_links
+        .iter()
+        .map(|part_link| part_link.alias.clone())
+        .collect::<Vec<String>>();
+    if part_aliases(previous_business_key) != part_aliases(business_key) {
+        error!("Business key {} changed its parts from ({}) to ({}), which would rehash the rows of {3}.  Roll back the builds of {3}, or give the new parts another business key name.",
+            business_key.name, part_aliases(previous_business_key).join(", "), part_aliases(business_key).join(", "), naming.hub(&business_key.name));
     }
 
-    // TODO: Map sats to dv_business_key_ddl_sql
-    for satellite_sql in satellite_sqls {
-        dv_business_key_ddl_sql.push_str(&satellite_sql.1);
+    // Sat Changes
+    let parent_hash_key = naming.hub_hash_key(&business_key.name);
+    dv_business_key_ddl_sql.push_str(&build_sql_satellite_changes(dv_schema, &parent_hash_key, &previous_business_key.descriptors, &business_key.descriptors));
+
+    // Status Sat Changes
+    if business_key.status_tracking && !previous_business_key.status_tracking {
+        dv_business_key_ddl_sql.push_str(&build_sql_status_satellite(dv_schema, business_key));
     }
-    // dv_business_key_ddl_sql.push_str(string)
+
+    // PIT Changes
+    dv_business_key_ddl_sql.push_str(&build_sql_pit(dv_schema, business_key));
+
+    // Current View Changes
+    dv_business_key_ddl_sql.push_str(&build_sql_current_views(dv_schema, business_key));
 
     dv_business_key_ddl_sql
 }
 
+// Adds new descriptors to existing satellites and creates satellites that did not exist before.
+fn build_sql_satellite_changes(dv_schema: &DVSchema, parent_hash_key: &String, previous_descriptors: &Vec<Descriptor>, descriptors: &Vec<Descriptor>) -> String {
+
+    let mut dv_satellites_ddl_sql = String::new();
+
+    let mut new_satellite_descriptors: Vec<Descriptor> = Vec::new();
+
+    for descriptor in descriptors {
+        let satellite_key = get_satellite_key(descriptor);
+
+        let previous_satellite_descriptors: Vec<&Descriptor> = previous_descriptors
+            .iter()
+            .filter(|previous_descriptor| get_satellite_key(previous_descriptor) == satellite_key)
+            .collect();
+
+        if previous_satellite_descriptors.is_empty() {
+            new_satellite_descriptors.push(descriptor.clone());
+        } else if !previous_satellite_descriptors.iter().any(|previous_descriptor| previous_descriptor.descriptor_link.alias == descriptor.descriptor_link.alias) {
+            let desc_column_type = &descriptor.descriptor_link.source_column.as_ref().unwrap().column_type_name;
+            dv_satellites_ddl_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {};
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &dv_schema.naming.descriptor_satellite(descriptor)), quote_identifier(&descriptor.descriptor_link.alias), desc_column_type));
+        }
+    }
+
+    dv_satellites_ddl_sql.push_str(&build_sql_satellites(dv_schema, parent_hash_key, &new_satellite_descriptors));
+
+    dv_satellites_ddl_sql
+}
+
+// Identifies the satellite of a descriptor, regardless of naming.  Tables are named with Naming::descriptor_satellite.
+pub fn get_satellite_key(descriptor: &Descriptor) -> String {
+    descriptor.orbit.clone() + if descriptor.is_sensitive { "_sensitive" } else { "" }
+}
+
+// Carries business keys, business key parts and descriptors from the previous schema into dv_schema
+// when they are missing, so the new schema version still describes every table that was built.
+fn dv_schema_merge_previous(dv_schema: &mut DVSchema, previous_dv_schema: &DVSchema) {
+
+    dv_schema.create_timestamp_gmt = previous_dv_schema.create_timestamp_gmt;
+
+    for previous_business_key in &previous_dv_schema.business_keys {
+
+        match dv_schema.business_keys.iter_mut().find(|business_key| business_key.name == previous_business_key.name) {
+            // Business key parts are not merged, as a hub's parts may not change between builds.
+            Some(business_key) => {
+                for previous_descriptor in &previous_business_key.descriptors {
+                    let is_missing = !business_key.descriptors.iter().any(|descriptor| {
+                        get_satellite_key(descriptor) == get_satellite_key(previous_descriptor) &&
+                        descriptor.descriptor_link.alias == previous_descriptor.descriptor_link.alias
+                    });
+                    if is_missing {
+                        business_key.descriptors.push(previous_descriptor.clone());
+                    }
+                }
+            }
+            None => {
+                dv_schema.business_keys.push(previous_business_key.clone());
+            }
+        }
+    }
+
+    for previous_link in &previous_dv_schema.links {
+        match dv_schema.links.iter_mut().find(|link| link.name == previous_link.name) {
+            Some(link) => {
+                for previous_descriptor in &previous_link.descriptors {
+                    let is_missing = !link.descriptors.iter().any(|descriptor| {
+                        get_satellite_key(descriptor) == get_satellite_key(previous_descriptor) &&
+                        descriptor.descriptor_link.alias == previous_descriptor.descriptor_link.alias
+                    });
+                    if is_missing {
+                        link.descriptors.push(previous_descriptor.clone());
+                    }
+                }
+            }
+            None => {
+                dv_schema.links.push(previous_link.clone());
+            }
+        }
+    }
+}
+
+fn build_sql_from_link(dv_schema: &DVSchema, link: &Link) -> String {
+    let naming = &dv_schema.naming;
+
+    let mut link_hub_hks = String::new();
+
+    for hub_reference in &link.hub_references {
+        let r = format!(r#",
+            {} VARCHAR NOT NULL"#, quote_identifier(naming.hub_hash_key(&hub_reference.business_key_name)));
+        link_hub_hks.push_str(&r);
+    }
+
+    let link_sql = format!(r#"
+        CREATE TABLE {} (
+            {} VARCHAR NOT NULL,
+            load_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+            record_source VARCHAR NOT NULL{}
+        );
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &naming.link(&link.name)), quote_identifier(naming.link_hash_key(&link.name)), link_hub_hks);
+
+    // Sat Buildout
+    let parent_hash_key = naming.link_hash_key(&link.name);
+
+    link_sql + &build_sql_satellites(dv_schema, &parent_hash_key, &link.descriptors) + &build_sql_bridge(dv_schema, link)
+}
+
+// Kimball dimensions for a business key.  Sensitive descriptors are kept in a separate dim_<name>_sensitive
+// dimension.  Statements are idempotent so descriptors added on later builds are picked up as new columns.
+fn build_sql_dimensions(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let naming = &dv_schema.naming;
+
+    let mut dimensions_sql = String::new();
+
+    for (dimension_name, descriptors) in dv_dimensions(naming, business_key) {
+
+        let dimension_table = quote_qualified_identifier(&dv_schema.dw_schema, &dimension_name);
+
+        dimensions_sql.push_str(&format!(r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            {} BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+            {} VARCHAR NOT NULL,
+            valid_from TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+            valid_to TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+            is_current BOOLEAN NOT NULL,
+            record_source VARCHAR NOT NULL,
+            {} VARCHAR NOT NULL
+        );
+    "#, dimension_table, 
+        quote_identifier(naming.surrogate_key(&business_key.name, &dimension_name)), 
+        quote_identifier(naming.dimension_hash_key(&business_key.name)), 
+        quote_identifier(naming.hash_diff(&business_key.name, &dimension_name))));
+
+        for part_link in &business_key.business_key_part_links {
+            dimensions_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} VARCHAR;
+    "#, dimension_table, quote_identifier(naming.business_key(&part_link.alias))));
+        }
+
+        for descriptor in descriptors {
+            let desc_column_type = &descriptor.descriptor_link.source_column.as_ref().unwrap().column_type_name;
+            dimensions_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {};
+    "#, dimension_table, quote_identifier(&descriptor.descriptor_link.alias), desc_column_type));
+        }
+    }
+
+    dimensions_sql
+}
+
+// Kimball fact table for a link, referencing the surrogate keys of its dimensions with the link's descriptors as measures.
+fn build_sql_fact(dv_schema: &DVSchema, link: &Link) -> String {
+    let naming = &dv_schema.naming;
+    let fact_name = naming.fact(&link.name);
+    let fact_table = quote_qualified_identifier(&dv_schema.dw_schema, &fact_name);
+
+    let mut fact_sql = format!(r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            {} BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+            load_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+            record_source VARCHAR NOT NULL,
+            {} VARCHAR NOT NULL
+        );
+    "#, fact_table, quote_identifier(naming.surrogate_key(&link.name, &fact_name)), quote_identifier(naming.hash_diff(&link.name, &fact_name)));
+
+    for hub_reference in &link.hub_references {
+        let dimension_name = naming.dimension(&hub_reference.business_key_name);
+        fact_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} BIGINT;
+    "#, fact_table, quote_identifier(naming.surrogate_key(&hub_reference.business_key_name, &dimension_name))));
+    }
+
+    for descriptor in &link.descriptors {
+        let desc_column_type = &descriptor.descriptor_link.source_column.as_ref().unwrap().column_type_name;
+        fact_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {};
+    "#, fact_table, quote_identifier(&descriptor.descriptor_link.alias), desc_column_type));
+    }
+
+    fact_sql
+}
+
+// Views joining a hub to the latest row of each of its satellites, with business key parts under their source
+// column names.  Sensitive satellites are joined in a separate v_<name>_sensitive_current view.
+pub fn build_sql_current_views(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let dw_schema = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+    let hub_hash_key = quote_identifier(naming.hub_hash_key(&business_key.name));
+
+    let mut views_sql = String::new();
+
+    let (sensitive_descriptors, descriptors): (Vec<&Descriptor>, Vec<&Descriptor>) = business_key.descriptors
+        .iter()
+        .partition(|descriptor| descriptor.is_sensitive);
+
+    let mut views = vec![(naming.current_view(&business_key.name), descriptors)];
+    if !sensitive_descriptors.is_empty() {
+        views.push((naming.sensitive_current_view(&business_key.name), sensitive_descriptors));
+    }
+
+    let mut hub_bk_cols = String::new();
+    for part_link in &business_key.business_key_part_links {
+        hub_bk_cols.push_str(&format!(r#",
+            hub.{} AS {}"#, quote_identifier(naming.business_key(&part_link.alias)), quote_identifier(&part_link.source_columns[0].column_name)));
+    }
+
+    for (view_name, descriptors) in views {
+
+        // Satellites are aliased by their position, as their names need not be valid aliases.
+        let mut satellite_names: Vec<String> = Vec::new();
+        let mut sat_cols = String::new();
+        for descriptor in descriptors {
+            let satellite_name = naming.descriptor_satellite(descriptor);
+            let satellite_index = match satellite_names.iter().position(|name| *name == satellite_name) {
+                Some(satellite_index) => satellite_index,
+                None => {
+                    satellite_names.push(satellite_name);
+                    satellite_names.len() - 1
+                }
+            };
+            sat_cols.push_str(&format!(r#",
+            sat_{}.{}"#, satellite_index, quote_identifier(&descriptor.descriptor_link.alias)));
+        }
+
+        let mut sat_joins = String::new();
+        for (satellite_index, satellite_name) in satellite_names.iter().enumerate() {
+            sat_joins.push_str(&format!(r#"
+        LEFT JOIN LATERAL (
+            SELECT *
+            FROM {} AS sat
+            WHERE sat.{} = hub.{}
+            ORDER BY sat.load_ts DESC
+            LIMIT 1
+        ) AS sat_{} ON TRUE"#, quote_qualified_identifier(dw_schema, satellite_name), hub_hash_key, hub_hash_key, satellite_index));
+        }
+
+        views_sql.push_str(&format!(r#"
+        CREATE OR REPLACE VIEW {} AS
+        SELECT
+            hub.{}{}{}
+        FROM {} AS hub{}
+        WHERE hub.record_source <> 'SYSTEM';
+    "#, quote_qualified_identifier(dw_schema, &view_name), hub_hash_key, hub_bk_cols, sat_cols, quote_qualified_identifier(dw_schema, &naming.hub(&business_key.name)), sat_joins));
+    }
+
+    views_sql
+}
+
+// Bridge table holding, per snapshot, the link keys along with the hub keys they relate.
+fn build_sql_bridge(dv_schema: &DVSchema, link: &Link) -> String {
+    let naming = &dv_schema.naming;
+
+    let mut bridge_hub_hks = String::new();
+    for hub_reference in &link.hub_references {
+        bridge_hub_hks.push_str(&format!(r#",
+            {} VARCHAR NOT NULL"#, quote_identifier(naming.hub_hash_key(&hub_reference.business_key_name))));
+    }
+
+    let bridge_table = quote_qualified_identifier(&dv_schema.dw_schema, &naming.bridge(&link.name));
+    let mut bridge_sql = format!(r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            {} VARCHAR NOT NULL,
+            snapshot_ts TIMESTAMP WITHOUT TIME ZONE NOT NULL{}
+        );
+    "#, bridge_table, quote_identifier(naming.link_hash_key(&link.name)), bridge_hub_hks);
+
+    for (_, satellite_name) in dv_bridge_satellite_names(dv_schema, link) {
+        bridge_sql.push_str(&format!(r#"
+        ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} TIMESTAMP WITHOUT TIME ZONE;
+    "#, bridge_table, quote_identifier(dv_pit_load_ts_column(&satellite_name))));
+    }
+
+    bridge_sql
+}
+
+fn build_sql_from_link_changes(dv_schema: &DVSchema, previous_link: Option<&Link>, link: &Link) -> String {
+    match previous_link {
+        Some(previous_link) => {
+            let parent_hash_key = dv_schema.naming.link_hash_key(&link.name);
+            build_sql_satellite_changes(dv_schema, &parent_hash_key, &previous_link.descriptors, &link.descriptors) + &build_sql_bridge(dv_schema, link)
+        }
+        None => build_sql_from_link(dv_schema, link),
+    }
+}
+
+// Indexes on the columns loads look rows up by, one per DW table.  Statements are idempotent so tables added on
+// later builds are picked up.
+pub fn build_sql_indexes(dv_schema: &DVSchema) -> String {
+    let naming = &dv_schema.naming;
+
+    // DW tables along with their indexed columns.
+    let mut table_indexes: Vec<(String, Vec<String>)> = Vec::new();
+
+    match dv_schema.dw_model {
+        DWModel::DataVault => {
+            for business_key in &dv_schema.business_keys {
+                let hub_hash_key = naming.hub_hash_key(&business_key.name);
+                table_indexes.push((naming.hub(&business_key.name), vec![hub_hash_key.clone()]));
+                for satellite_name in dv_pit_satellite_names(naming, business_key) {
+                    table_indexes.push((satellite_name, vec![hub_hash_key.clone(), "load_ts".to_string()]));
+                }
+                table_indexes.push((naming.pit(&business_key.name), vec![hub_hash_key, "snapshot_ts".to_string()]));
+            }
+
+            for link in &dv_schema.links {
+                let link_hash_key = naming.link_hash_key(&link.name);
+                table_indexes.push((naming.link(&link.name), vec![link_hash_key.clone()]));
+                for descriptor in &link.descriptors {
+                    let satellite_name = naming.descriptor_satellite(descriptor);
+                    if !table_indexes.iter().any(|(table_name, _)| table_name == &satellite_name) {
+                        table_indexes.push((satellite_name, vec![link_hash_key.clone(), "load_ts".to_string()]));
+                    }
+                }
+                table_indexes.push((naming.bridge(&link.name), vec![link_hash_key, "snapshot_ts".to_string()]));
+            }
+        }
+        DWModel::Kimball => {
+            for business_key in &dv_schema.business_keys {
+                for (dimension_name, _) in dv_dimensions(naming, business_key) {
+                    table_indexes.push((dimension_name, vec![naming.dimension_hash_key(&business_key.name), "is_current".to_string()]));
+                }
+            }
+
+            for link in &dv_schema.links {
+                let fact_name = naming.fact(&link.name);
+                let fact_hash_diff = naming.hash_diff(&link.name, &fact_name);
+                table_indexes.push((fact_name, vec![fact_hash_diff]));
+            }
+        }
+    }
+
+    table_indexes
+        .iter()
+        .map(|(table_name, columns)| format!(r#"
+        CREATE INDEX IF NOT EXISTS {} ON {} ({});
+    "#, 
+            quote_identifier(index_name(table_name)), 
+            quote_qualified_identifier(&dv_schema.dw_schema, table_name), 
+            columns.iter().map(quote_identifier).collect::<Vec<String>>().join(", ")))
+        .collect()
+}
+
+// <table>_idx, or for long table names a prefix of the table name with a hash of the whole name, so index names fit
+// within Postgres' 63 byte identifiers without two tables' indexes being truncated to the same name.
+fn index_name(table_name: &str) -> String {
+    const MAX_IDENTIFIER_BYTES: usize = 63;
+
+    let index_name = format!("{}_idx", table_name);
+    if index_name.len() <= MAX_IDENTIFIER_BYTES {
+        return index_name;
+    }
+
+    let table_hash = Uuid::new_v5(&Uuid::NAMESPACE_OID, table_name.as_bytes()).simple().to_string();
+    let suffix = format!("_{}_idx", &table_hash[..8]);
+    let mut prefix_len = MAX_IDENTIFIER_BYTES - suffix.len();
+    while !table_name.is_char_boundary(prefix_len) {
+        prefix_len -= 1;
+    }
+    format!("{}{}", &table_name[..prefix_len], suffix)
+}
+
 #[derive(Debug, PartialEq)]
 enum ColumnCategory {
     BusinessKeyPart,
@@ -477,4 +1424,6 @@ struct TransformerObject {
     table_oid: u32,
     column_ordinal_position: i16,
     column_category: ColumnCategory,
+    // Set with auto_dw.update_context.
+    alias: Option<String>,
 }
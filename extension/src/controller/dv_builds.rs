@@ -0,0 +1,398 @@
+use pgrx::prelude::*;
+use pgrx::spi::{quote_identifier, quote_qualified_identifier};
+
+use crate::model::queries;
+use crate::model::dv_schema::*;
+
+use super::dv_builder::build_sql_current_views;
+use super::dv_health::get_dw_tables;
+use super::dv_loader::*;
+
+// A build recorded by go, along with the DW tables it created or altered.
+pub struct Build {
+    pub build_id: String,
+    pub build_ts: String,
+    pub dw_schema: Option<String>,
+    pub status: String,
+    pub tables_created: Vec<String>,
+    pub tables_altered: Vec<String>,
+}
+
+// A build as recorded in the build calls and the repo.  Builds that failed before reaching the repo have no schema.
+struct RepoBuild {
+    build_id: String,
+    build_ts: String,
+    rolled_back_at: Option<String>,
+    dv_schema: Option<DVSchema>,
+}
+
+// A DW table created by a build, or the columns a build added to an existing table.
+struct DWTableChange {
+    table_name: String,
+    is_created: bool,
+    columns: Vec<String>,
+}
+
+pub fn dv_builds() -> Vec<Build> {
+    let repo_builds = get_repo_builds();
+
+    repo_builds
+        .iter()
+        .enumerate()
+        .map(|(index, repo_build)| {
+            let status = match (&repo_build.rolled_back_at, &repo_build.dv_schema) {
+                (Some(_), _) => "Rolled Back",
+                (None, Some(_)) => "Built",
+                (None, None) => "Failed",
+            };
+
+            let dw_table_changes = match &repo_build.dv_schema {
+                Some(dv_schema) => {
+                    let previous_dv_schema = get_previous_dv_schema(&repo_builds[..index], repo_build, dv_schema);
+                    get_dw_table_changes(dv_schema, previous_dv_schema)
+                }
+                None => Vec::new(),
+            };
+
+            let (tables_created, tables_altered): (Vec<DWTableChange>, Vec<DWTableChange>) = dw_table_changes
+                .into_iter()
+                .partition(|dw_table_change| dw_table_change.is_created);
+
+            Build {
+                build_id: repo_build.build_id.clone(),
+                build_ts: repo_build.build_ts.clone(),
+                dw_schema: repo_build.dv_schema.as_ref().map(|dv_schema| dv_schema.dw_schema.clone()),
+                status: status.to_string(),
+                tables_created: tables_created.into_iter().map(|dw_table_change| dw_table_change.table_name).collect(),
+                tables_altered: tables_altered.into_iter().map(|dw_table_change| dw_table_change.table_name).collect(),
+            }
+        })
+        .collect()
+}
+
+// Drops the tables a build created and the columns it added, then marks the build as rolled back.  Only the
+// latest build of a DW schema can be rolled back, so the previous build describes the tables left in place.
+pub fn dv_rollback(build_id: &String) -> String {
+    let repo_builds = get_repo_builds();
+
+    let (index, repo_build) = repo_builds
+        .iter()
+        .enumerate()
+        .find(|(_, repo_build)| &repo_build.build_id == build_id)
+        .unwrap_or_else(|| error!("Build ID: {} not found.", build_id));
+
+    let dv_schema = repo_build.dv_schema.as_ref()
+        .unwrap_or_else(|| error!("Build ID: {} did not build any tables and cannot be rolled back.", build_id));
+
+    match dv_load_latest_schema(&dv_schema.dw_schema) {
+        Some((latest_build_id, _)) if &latest_build_id == build_id => {}
+        _ => error!("Build ID: {} is not the latest build of DW schema \"{}\".  Roll back later builds first.", build_id, dv_schema.dw_schema),
+    }
+
+    let previous_dv_schema = get_previous_dv_schema(&repo_builds[..index], repo_build, dv_schema);
+    let rollback_sql = build_sql_rollback(dv_schema, previous_dv_schema);
+
+    if !rollback_sql.trim().is_empty() {
+        if let Err(e) = Spi::run(&rollback_sql) {
+            error!("Build ID: {} could not be rolled back: {}", build_id, e);
+        }
+    }
+
+    for query in [queries::ROLLBACK_BUILD, queries::ROLLBACK_BUILD_CALL] {
+        if let Err(e) = Spi::run_with_args(query, Some(vec![
+            (PgOid::from(pg_sys::TEXTOID), build_id.into_datum()),
+        ])) {
+            error!("Build ID: {} could not be marked as rolled back: {}", build_id, e);
+        }
+    }
+
+    rollback_sql
+}
+
+fn build_sql_rollback(dv_schema: &DVSchema, previous_dv_schema: Option<&DVSchema>) -> String {
+    let mut rollback_sql = String::new();
+
+    // Current views select from satellites, so they are dropped first and recreated from the previous build.
+    if dv_schema.dw_model == DWModel::DataVault {
+        for business_key in &dv_schema.business_keys {
+            rollback_sql.push_str(&format!(r#"
+        DROP VIEW IF EXISTS {};
+        DROP VIEW IF EXISTS {};
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &dv_schema.naming.current_view(&business_key.name)), quote_qualified_identifier(&dv_schema.dw_schema, &dv_schema.naming.sensitive_current_view(&business_key.name))));
+        }
+    }
+
+    for dw_table_change in get_dw_table_changes(dv_schema, previous_dv_schema) {
+        if dw_table_change.is_created {
+            rollback_sql.push_str(&format!(r#"
+        DROP TABLE IF EXISTS {};
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &dw_table_change.table_name)));
+        } else {
+            for column_name in &dw_table_change.columns {
+                rollback_sql.push_str(&format!(r#"
+        ALTER TABLE {} DROP COLUMN IF EXISTS {};
+    "#, quote_qualified_identifier(&dv_schema.dw_schema, &dw_table_change.table_name), quote_identifier(column_name)));
+            }
+        }
+    }
+
+    if let Some(previous_dv_schema) = previous_dv_schema {
+        if dv_schema.dw_model == DWModel::DataVault && previous_dv_schema.dw_model == DWModel::DataVault {
+            for business_key in &previous_dv_schema.business_keys {
+                rollback_sql.push_str(&build_sql_current_views(previous_dv_schema, business_key));
+            }
+        }
+    }
+
+    rollback_sql
+}
+
+fn get_dw_table_changes(dv_schema: &DVSchema, previous_dv_schema: Option<&DVSchema>) -> Vec<DWTableChange> {
+    let previous_dw_tables = previous_dv_schema
+        .map(get_dw_tables)
+        .unwrap_or_default();
+
+    let mut dw_table_changes: Vec<DWTableChange> = Vec::new();
+
+    for dw_table in get_dw_tables(dv_schema) {
+        match previous_dw_tables.iter().find(|previous_dw_table| previous_dw_table.name == dw_table.name) {
+            None => dw_table_changes.push(DWTableChange {
+                table_name: dw_table.name,
+                is_created: true,
+                columns: Vec::new(),
+            }),
+            Some(previous_dw_table) => {
+                let columns: Vec<String> = dw_table.columns
+                    .into_iter()
+                    .map(|(column_name, _)| column_name)
+                    .filter(|column_name| !previous_dw_table.columns.iter().any(|(previous_column_name, _)| previous_column_name == column_name))
+                    .collect();
+                if !columns.is_empty() {
+                    dw_table_changes.push(DWTableChange {
+                        table_name: dw_table.name,
+                        is_created: false,
+                        columns,
+                    });
+                }
+            }
+        }
+    }
+
+    dw_table_changes
+}
+
+// The build of the same DW schema, model, and naming that was latest when repo_build ran, whose tables repo_build
+// built on.  Builds rolled back before then are skipped, as are builds in another model or naming, whose tables
+// were rolled back before repo_build could run.
+fn get_previous_dv_schema<'a>(earlier_repo_builds: &'a [RepoBuild], repo_build: &RepoBuild, dv_schema: &DVSchema) -> Option<&'a DVSchema> {
+    earlier_repo_builds
+        .iter()
+        .rev()
+        .filter(|earlier_repo_build| match &earlier_repo_build.rolled_back_at {
+            Some(rolled_back_at) => rolled_back_at > &repo_build.build_ts,
+            None => true,
+        })
+        .filter_map(|earlier_repo_build| earlier_repo_build.dv_schema.as_ref())
+        .find(|earlier_dv_schema| {
+            earlier_dv_schema.dw_schema == dv_schema.dw_schema &&
+            earlier_dv_schema.dw_model == dv_schema.dw_model &&
+            earlier_dv_schema.naming == dv_schema.naming
+        })
+}
+
+fn get_repo_builds() -> Vec<RepoBuild> {
+    Spi::connect(|client| {
+        let mut repo_builds: Vec<RepoBuild> = Vec::new();
+
+        match client.select(queries::BUILDS, None, None) {
+            Ok(results) => {
+                for result in results {
+                    let build_id = result.get_datum_by_ordinal(1).unwrap().value::<String>().unwrap().unwrap();
+                    let build_ts = result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap().unwrap_or_default();
+                    let rolled_back_at = result.get_datum_by_ordinal(3).unwrap().value::<String>().unwrap();
+                    let dv_schema = result.get_datum_by_ordinal(4).unwrap().value::<pgrx::Json>().unwrap()
+                        .and_then(|schema_json| serde_json::from_value::<DVSchema>(schema_json.0).ok());
+                    repo_builds.push(RepoBuild { build_id, build_ts, rolled_back_at, dv_schema });
+                }
+            }
+            Err(e) => {
+                log!("Error reading builds: {:?}", e);
+            }
+        }
+
+        repo_builds
+    })
+}
+
+// A difference between the DVSchemas of two builds.  parent is the business key or link an object belongs to.
+pub struct SchemaChange {
+    pub change_type: String,
+    pub object_type: String,
+    pub parent: Option<String>,
+    pub name: String,
+    pub detail: String,
+}
+
+// Differences from build_id_a to build_id_b.  Business keys and links are matched by name, and their parts,
+// references, and descriptors by alias or business key name.
+pub fn dv_schema_diff(build_id_a: &String, build_id_b: &String) -> Vec<SchemaChange> {
+    let load = |build_id: &String| dv_load_schema_from_build_id(build_id)
+        .unwrap_or_else(|| error!("Build ID: {} not found or did not build any tables.", build_id));
+    let dv_schema_a = load(build_id_a);
+    let dv_schema_b = load(build_id_b);
+
+    let mut schema_changes: Vec<SchemaChange> = Vec::new();
+
+    let schema_settings = [
+        ("DW Schema", dv_schema_a.dw_schema.clone(), dv_schema_b.dw_schema.clone()),
+        ("DW Model", format!("{:?}", dv_schema_a.dw_model), format!("{:?}", dv_schema_b.dw_model)),
+        ("Naming", serde_json::to_string(&dv_schema_a.naming).unwrap_or_default(), serde_json::to_string(&dv_schema_b.naming).unwrap_or_default()),
+    ];
+    for (name, value_a, value_b) in schema_settings {
+        if value_a != value_b {
+            schema_changes.push(schema_change("Changed", "Schema", None, name, format!("{} -> {}", value_a, value_b)));
+        }
+    }
+
+    let business_key_pairs = match_by(&dv_schema_a.business_keys, &dv_schema_b.business_keys, |business_key| business_key.name.clone());
+    for (business_key_a, business_key_b) in business_key_pairs {
+        match (business_key_a, business_key_b) {
+            (None, Some(business_key)) => schema_changes.push(schema_change("Added", "Business Key", None, &business_key.name, String::new())),
+            (Some(business_key), None) => schema_changes.push(schema_change("Removed", "Business Key", None, &business_key.name, String::new())),
+            (Some(business_key_a), Some(business_key_b)) => {
+                let parent = Some(business_key_b.name.clone());
+
+                if business_key_a.status_tracking != business_key_b.status_tracking {
+                    schema_changes.push(schema_change("Changed", "Business Key", None, &business_key_b.name, format!("Status Tracking: {} -> {}", business_key_a.status_tracking, business_key_b.status_tracking)));
+                }
+
+                let part_link_pairs = match_by(&business_key_a.business_key_part_links, &business_key_b.business_key_part_links, |part_link| part_link.alias.clone());
+                for (part_link_a, part_link_b) in part_link_pairs {
+                    match (part_link_a, part_link_b) {
+                        (None, Some(part_link)) => schema_changes.push(schema_change("Added", "Business Key Part", parent.clone(), &part_link.alias, column_label(part_link.source_columns.first()))),
+                        (Some(part_link), None) => schema_changes.push(schema_change("Removed", "Business Key Part", parent.clone(), &part_link.alias, column_label(part_link.source_columns.first()))),
+                        (Some(part_link_a), Some(part_link_b)) => {
+                            let details = column_changes(part_link_a.source_columns.first(), part_link_b.source_columns.first(), part_link_a.target_column.as_ref(), part_link_b.target_column.as_ref());
+                            if !details.is_empty() {
+                                schema_changes.push(schema_change("Changed", "Business Key Part", parent.clone(), &part_link_b.alias, details.join(", ")));
+                            }
+                        }
+                        (None, None) => {}
+                    }
+                }
+
+                schema_changes.extend(descriptor_changes(&parent, &business_key_a.descriptors, &business_key_b.descriptors));
+            }
+            (None, None) => {}
+        }
+    }
+
+    let link_pairs = match_by(&dv_schema_a.links, &dv_schema_b.links, |link| link.name.clone());
+    for (link_a, link_b) in link_pairs {
+        match (link_a, link_b) {
+            (None, Some(link)) => schema_changes.push(schema_change("Added", "Link", None, &link.name, String::new())),
+            (Some(link), None) => schema_changes.push(schema_change("Removed", "Link", None, &link.name, String::new())),
+            (Some(link_a), Some(link_b)) => {
+                let parent = Some(link_b.name.clone());
+
+                let hub_reference_pairs = match_by(&link_a.hub_references, &link_b.hub_references, |hub_reference| hub_reference.business_key_name.clone());
+                for (hub_reference_a, hub_reference_b) in hub_reference_pairs {
+                    match (hub_reference_a, hub_reference_b) {
+                        (None, Some(hub_reference)) => schema_changes.push(schema_change("Added", "Hub Reference", parent.clone(), &hub_reference.business_key_name, column_label(hub_reference.source_columns.first()))),
+                        (Some(hub_reference), None) => schema_changes.push(schema_change("Removed", "Hub Reference", parent.clone(), &hub_reference.business_key_name, column_label(hub_reference.source_columns.first()))),
+                        (Some(hub_reference_a), Some(hub_reference_b)) => {
+                            let details = column_changes(hub_reference_a.source_columns.first(), hub_reference_b.source_columns.first(), hub_reference_a.target_column.as_ref(), hub_reference_b.target_column.as_ref());
+                            if !details.is_empty() {
+                                schema_changes.push(schema_change("Changed", "Hub Reference", parent.clone(), &hub_reference_b.business_key_name, details.join(", ")));
+                            }
+                        }
+                        (None, None) => {}
+                    }
+                }
+
+                schema_changes.extend(descriptor_changes(&parent, &link_a.descriptors, &link_b.descriptors));
+            }
+            (None, None) => {}
+        }
+    }
+
+    schema_changes
+}
+
+fn schema_change(change_type: &str, object_type: &str, parent: Option<String>, name: &str, detail: String) -> SchemaChange {
+    SchemaChange {
+        change_type: change_type.to_string(),
+        object_type: object_type.to_string(),
+        parent,
+        name: name.to_string(),
+        detail,
+    }
+}
+
+// Pairs of matching items from a and b, in the order of a followed by the items only in b.
+fn match_by<'a, T, K: PartialEq>(items_a: &'a Vec<T>, items_b: &'a Vec<T>, key: impl Fn(&T) -> K) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut pairs: Vec<(Option<&'a T>, Option<&'a T>)> = items_a
+        .iter()
+        .map(|item_a| (Some(item_a), items_b.iter().find(|item_b| key(item_b) == key(item_a))))
+        .collect();
+
+    for item_b in items_b {
+        if !items_a.iter().any(|item_a| key(item_a) == key(item_b)) {
+            pairs.push((None, Some(item_b)));
+        }
+    }
+
+    pairs
+}
+
+fn descriptor_changes(parent: &Option<String>, descriptors_a: &Vec<Descriptor>, descriptors_b: &Vec<Descriptor>) -> Vec<SchemaChange> {
+    let mut schema_changes: Vec<SchemaChange> = Vec::new();
+
+    for (descriptor_a, descriptor_b) in match_by(descriptors_a, descriptors_b, |descriptor| descriptor.descriptor_link.alias.clone()) {
+        match (descriptor_a, descriptor_b) {
+            (None, Some(descriptor)) => schema_changes.push(schema_change("Added", "Descriptor", parent.clone(), &descriptor.descriptor_link.alias, column_label(descriptor.descriptor_link.source_column.as_ref()))),
+            (Some(descriptor), None) => schema_changes.push(schema_change("Removed", "Descriptor", parent.clone(), &descriptor.descriptor_link.alias, column_label(descriptor.descriptor_link.source_column.as_ref()))),
+            (Some(descriptor_a), Some(descriptor_b)) => {
+                let mut details: Vec<String> = Vec::new();
+                if descriptor_a.orbit != descriptor_b.orbit {
+                    details.push(format!("Orbit: {} -> {}", descriptor_a.orbit, descriptor_b.orbit));
+                }
+                if descriptor_a.is_sensitive != descriptor_b.is_sensitive {
+                    details.push(format!("Sensitive: {} -> {}", descriptor_a.is_sensitive, descriptor_b.is_sensitive));
+                }
+                details.extend(column_changes(
+                    descriptor_a.descriptor_link.source_column.as_ref(), descriptor_b.descriptor_link.source_column.as_ref(),
+                    descriptor_a.descriptor_link.target_column.as_ref(), descriptor_b.descriptor_link.target_column.as_ref()));
+                if !details.is_empty() {
+                    schema_changes.push(schema_change("Changed", "Descriptor", parent.clone(), &descriptor_b.descriptor_link.alias, details.join(", ")));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    schema_changes
+}
+
+fn column_label(column_data: Option<&ColumnData>) -> String {
+    match column_data {
+        Some(column_data) => format!("{}.{}.{} {}", column_data.schema_name, column_data.table_name, column_data.column_name, column_data.column_type_name),
+        None => "-".to_string(),
+    }
+}
+
+// Changes to the source column an object is loaded from and to the type of its target column.
+fn column_changes(source_column_a: Option<&ColumnData>, source_column_b: Option<&ColumnData>, target_column_a: Option<&ColumnData>, target_column_b: Option<&ColumnData>) -> Vec<String> {
+    let mut details: Vec<String> = Vec::new();
+
+    if column_label(source_column_a) != column_label(source_column_b) {
+        details.push(format!("Source Column: {} -> {}", column_label(source_column_a), column_label(source_column_b)));
+    }
+
+    let target_type = |target_column: Option<&ColumnData>| target_column.map(|target_column| target_column.column_type_name.clone()).unwrap_or_else(|| "-".to_string());
+    if target_type(target_column_a) != target_type(target_column_b) {
+        details.push(format!("Target Column Type: {} -> {}", target_type(target_column_a), target_type(target_column_b)));
+    }
+
+    details
+}
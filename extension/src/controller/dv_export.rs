@@ -0,0 +1,599 @@
+use pgrx::prelude::*;
+use pgrx::spi::{quote_identifier, quote_literal};
+
+use crate::model::dv_schema::*;
+
+use super::dv_builder::{build_sql_for_business_key, build_sql_for_link, build_sql_indexes};
+use super::dv_loader::*;
+
+// Script recreating a build from its DVSchema in an empty DW schema: the schema, table DDL, indexes, and the
+// initial load.  Tables are built as if no previous build existed, so the script holds the build's full model.
+pub fn dv_export_sql(build_id: &String) -> String {
+    let dv_schema = dv_load_schema_from_build_id(build_id)
+        .unwrap_or_else(|| error!("Build ID: {} not found or did not build any tables.", build_id));
+
+    let mut export_sql = format!(r#"-- pg_auto_dw export of Build ID: {}
+-- DW Schema: {} | DW Model: {:?} | Modified Date: {}
+"#, build_id, dv_schema.dw_schema, dv_schema.dw_model, dv_schema.modified_timestamp_gmt);
+
+    export_sql.push_str(&export_section("Schema", &format!(r#"
+        CREATE SCHEMA IF NOT EXISTS {};
+    "#, quote_identifier(&dv_schema.dw_schema))));
+
+    for business_key in &dv_schema.business_keys {
+        export_sql.push_str(&export_section(&format!("Business Key: {}", business_key.name), &build_sql_for_business_key(&dv_schema, None, business_key)));
+    }
+
+    for link in &dv_schema.links {
+        export_sql.push_str(&export_section(&format!("Link: {}", link.name), &build_sql_for_link(&dv_schema, None, link)));
+    }
+
+    export_sql.push_str(&export_section("Indexes", &build_sql_indexes(&dv_schema)));
+    export_sql.push_str(&export_section("Initial Load", &dv_data_loader_dml(&dv_schema)));
+    export_sql.push_str(&export_section("PIT and Bridge Refresh", &dv_pit_bridge_refresh_dml(&dv_schema)));
+
+    export_sql
+}
+
+// Statements under a comment header, with blank lines between statements collapsed.  Empty sections are left out.
+fn export_section(title: &str, sql: &str) -> String {
+    if sql.trim().is_empty() {
+        return String::new();
+    }
+
+    let statements: Vec<&str> = sql
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    format!("\n-- {}\n{}\n", title, statements.join("\n"))
+}
+
+const DBT_IF_INCREMENTAL: &str = "{% if is_incremental() %}";
+const DBT_IF_NOT_INCREMENTAL: &str = "{% if not is_incremental() %}";
+const DBT_END_IF: &str = "{% endif %}";
+
+// dbt project files, as (path, content), modeling the hubs, links, and satellites of a Data Vault build.
+// Models load incrementally with the hashing and change detection of dv_loader.
+pub fn dv_export_dbt(build_id: &String) -> Vec<(String, String)> {
+    let dv_schema = dv_load_schema_from_build_id(build_id)
+        .unwrap_or_else(|| error!("Build ID: {} not found or did not build any tables.", build_id));
+
+    if dv_schema.dw_model != DWModel::DataVault {
+        error!("Build ID: {} is a {:?} build.  dbt projects are only exported for Data Vault builds.", build_id, dv_schema.dw_model);
+    }
+
+    let naming = &dv_schema.naming;
+
+    let mut dbt_files: Vec<(String, String)> = Vec::new();
+    // Models along with their hash key column and whether it is unique.
+    let mut model_keys: Vec<(String, String, bool)> = Vec::new();
+
+    dbt_files.push(("dbt_project.yml".to_string(), dbt_project_yml(&dv_schema.dw_schema)));
+    dbt_files.push(("models/sources.yml".to_string(), dbt_sources_yml(&dv_schema)));
+
+    for business_key in &dv_schema.business_keys {
+        let hub_name = naming.hub(&business_key.name);
+        let hub_hash_key = naming.hub_hash_key(&business_key.name);
+
+        // Hash parts of the hub key in each source table of the business key.
+        let hub_hk_parts_sqls: Vec<((String, String), String)> = business_key.source_tables()
+            .into_iter()
+            .filter_map(|(schema_name, table_name)| {
+                let source_columns = business_key.source_columns_of(&schema_name, &table_name)?;
+                Some(((schema_name, table_name), dbt_hash_parts(source_columns.into_iter(), false)))
+            })
+            .collect();
+
+        dbt_files.push((format!("models/hubs/{}.sql", hub_name), dbt_hub_model(&dv_schema, business_key)));
+        model_keys.push((hub_name, hub_hash_key.clone(), true));
+
+        for (satellite_name, satellite_model) in dbt_satellite_models(&dv_schema, &hub_hash_key, &hub_hk_parts_sqls, &business_key.descriptors) {
+            dbt_files.push((format!("models/satellites/{}.sql", satellite_name), satellite_model));
+            model_keys.push((satellite_name, hub_hash_key.clone(), false));
+        }
+    }
+
+    for link in &dv_schema.links {
+        let link_name = naming.link(&link.name);
+        let link_hash_key = naming.link_hash_key(&link.name);
+        let link_hk_parts_sql = dbt_hash_parts(link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter()), true);
+
+        // Links are loaded from a single source table.
+        let link_hk_parts_sqls: Vec<((String, String), String)> = link.hub_references.iter()
+            .flat_map(|hub_reference| hub_reference.source_columns.iter())
+            .next()
+            .map(|source_column| ((source_column.schema_name.clone(), source_column.table_name.clone()), link_hk_parts_sql.clone()))
+            .into_iter()
+            .collect();
+
+        if let Some(link_model) = dbt_link_model(&dv_schema, link, &link_hk_parts_sql) {
+            dbt_files.push((format!("models/links/{}.sql", link_name), link_model));
+            model_keys.push((link_name, link_hash_key.clone(), true));
+        }
+
+        for (satellite_name, satellite_model) in dbt_satellite_models(&dv_schema, &link_hash_key, &link_hk_parts_sqls, &link.descriptors) {
+            dbt_files.push((format!("models/satellites/{}.sql", satellite_name), satellite_model));
+            model_keys.push((satellite_name, link_hash_key.clone(), false));
+        }
+    }
+
+    dbt_files.push(("models/schema.yml".to_string(), dbt_schema_yml(&model_keys)));
+
+    dbt_files
+}
+
+// Double quoted strings are valid in both YAML and Jinja, so names are written as JSON strings.
+fn dbt_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+// dbt project names may only hold letters, digits, and underscores.
+fn dbt_project_yml(dw_schema: &str) -> String {
+    let project_name: String = dw_schema
+        .chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character.to_ascii_lowercase() } else { '_' })
+        .collect();
+
+    format!(r#"name: {project_name}
+version: "1.0.0"
+config-version: 2
+profile: pg_auto_dw
+
+model-paths: ["models"]
+
+# Names are quoted as pg_auto_dw quotes them, so case and special characters are kept.
+quoting:
+  identifier: true
+
+# Point the profile's schema at DW schema {dw_schema}.  Loads hash with auto_dw.hash, so pg_auto_dw must be installed.
+models:
+  {project_name}:
+    +materialized: incremental
+"#, project_name = project_name, dw_schema = dbt_string(dw_schema))
+}
+
+fn dbt_sources_yml(dv_schema: &DVSchema) -> String {
+    // Source schemas along with their tables, in the order they are referenced.
+    let mut sources: Vec<(String, Vec<String>)> = Vec::new();
+
+    let source_columns = dv_schema.business_keys.iter()
+        .flat_map(|business_key| business_key.business_key_part_links.iter().flat_map(|part_link| part_link.source_columns.iter())
+            .chain(business_key.descriptors.iter().filter_map(|descriptor| descriptor.descriptor_link.source_column.as_ref())))
+        .chain(dv_schema.links.iter()
+            .flat_map(|link| link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter())
+                .chain(link.descriptors.iter().filter_map(|descriptor| descriptor.descriptor_link.source_column.as_ref()))));
+
+    for source_column in source_columns {
+        match sources.iter_mut().find(|(schema_name, _)| schema_name == &source_column.schema_name) {
+            Some((_, table_names)) => {
+                if !table_names.contains(&source_column.table_name) {
+                    table_names.push(source_column.table_name.clone());
+                }
+            }
+            None => sources.push((source_column.schema_name.clone(), vec![source_column.table_name.clone()])),
+        }
+    }
+
+    let mut sources_yml = String::from("version: 2\n\nsources:\n");
+    for (schema_name, table_names) in sources {
+        sources_yml.push_str(&format!("  - name: {}\n    schema: {}\n    quoting:\n      schema: true\n      identifier: true\n    tables:\n", dbt_string(&schema_name), dbt_string(&schema_name)));
+        for table_name in table_names {
+            sources_yml.push_str(&format!("      - name: {}\n", dbt_string(&table_name)));
+        }
+    }
+
+    sources_yml
+}
+
+fn dbt_schema_yml(model_keys: &Vec<(String, String, bool)>) -> String {
+    let mut schema_yml = String::from("version: 2\n\nmodels:\n");
+    for (model_name, hash_key, is_unique) in model_keys {
+        schema_yml.push_str(&format!("  - name: {}\n    columns:\n      - name: {}\n        quote: true\n        tests:\n", dbt_string(model_name), dbt_string(hash_key)));
+        if *is_unique {
+            schema_yml.push_str("          - unique\n");
+        }
+        schema_yml.push_str("          - not_null\n");
+    }
+    schema_yml
+}
+
+fn dbt_source(source_column: &ColumnData) -> String {
+    format!("{{{{ source({}, {}) }}}}", dbt_string(&source_column.schema_name), dbt_string(&source_column.table_name))
+}
+
+// Array parts hashed to a hub or link key, with unknown link references hashing to the hub's '-1' record.
+fn dbt_hash_parts<'a>(source_columns: impl Iterator<Item = &'a ColumnData>, is_link: bool) -> String {
+    source_columns
+        .map(|source_column| match is_link {
+            true => format!("COALESCE(stg.{}::TEXT, '-1')", quote_identifier(&source_column.column_name)),
+            false => format!("stg.{}::TEXT", quote_identifier(&source_column.column_name)),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Hubs select the business keys of each of their source tables, keeping one row per hash key.
+fn dbt_hub_model(dv_schema: &DVSchema, business_key: &BusinessKey) -> String {
+    let naming = &dv_schema.naming;
+    let hub_hash_key = quote_identifier(naming.hub_hash_key(&business_key.name));
+
+    let mut hub_bk_neg_1_sql = String::new();
+    let mut hub_bk_neg_2_sql = String::new();
+    for part_link in &business_key.business_key_part_links {
+        let business_key_column = quote_identifier(naming.business_key(&part_link.alias));
+        hub_bk_neg_1_sql.push_str(&format!(",\n    '-1'::TEXT AS {}", business_key_column));
+        hub_bk_neg_2_sql.push_str(&format!(",\n    '-2'::TEXT AS {}", business_key_column));
+    }
+
+    let mut hub_stg_sqls: Vec<String> = Vec::new();
+    for (schema_name, table_name) in business_key.source_tables() {
+        let source_columns = match business_key.source_columns_of(&schema_name, &table_name) {
+            Some(source_columns) => source_columns,
+            None => continue,
+        };
+
+        let mut hub_bk_parts_stg_sql = String::new();
+        for (part_link, source_column) in business_key.business_key_part_links.iter().zip(&source_columns) {
+            hub_bk_parts_stg_sql.push_str(&format!(",\n    stg.{}::TEXT AS {}", quote_identifier(&source_column.column_name), quote_identifier(naming.business_key(&part_link.alias))));
+        }
+
+        hub_stg_sqls.push(format!(r#"SELECT
+    auto_dw.hash(ARRAY_TO_STRING(ARRAY[{hub_hk_parts_sql}], ',')) AS {hub_hash_key},
+    (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP(6) AS load_ts,
+    {record_source}::TEXT AS record_source{hub_bk_parts_stg_sql}
+FROM {source} AS stg"#,
+            hub_hk_parts_sql = dbt_hash_parts(source_columns.iter().copied(), false),
+            record_source = quote_literal(&schema_name),
+            source = dbt_source(source_columns[0])));
+    }
+
+    format!(r#"{{{{ config(unique_key={unique_key}) }}}}
+
+WITH
+stg_data AS (
+SELECT DISTINCT ON (stg.{hub_hash_key}) stg.*
+FROM (
+{hub_stg_sql}
+) AS stg
+ORDER BY stg.{hub_hash_key}, stg.record_source
+)
+SELECT stg_data.* FROM stg_data
+{DBT_IF_INCREMENTAL}
+WHERE NOT EXISTS (SELECT 1 FROM {{{{ this }}}} AS hub WHERE hub.{hub_hash_key} = stg_data.{hub_hash_key})
+{DBT_END_IF}
+{DBT_IF_NOT_INCREMENTAL}
+UNION ALL
+SELECT
+    auto_dw.hash(ARRAY_TO_STRING(ARRAY[-1], ',')::TEXT) AS {hub_hash_key},
+    '0001-01-01'::TIMESTAMP(6) AS load_ts,
+    'SYSTEM'::TEXT AS record_source{hub_bk_neg_1_sql}
+UNION ALL
+SELECT
+    auto_dw.hash(ARRAY_TO_STRING(ARRAY[-2], ',')::TEXT) AS {hub_hash_key},
+    '0001-01-01'::TIMESTAMP(6) AS load_ts,
+    'SYSTEM'::TEXT AS record_source{hub_bk_neg_2_sql}
+{DBT_END_IF}
+"#,
+        unique_key = dbt_string(&naming.hub_hash_key(&business_key.name)),
+        hub_stg_sql = hub_stg_sqls.join("\nUNION\n"))
+}
+
+// None for links without hub references, as they have no source table.
+fn dbt_link_model(dv_schema: &DVSchema, link: &Link, link_hk_parts_sql: &str) -> Option<String> {
+    let naming = &dv_schema.naming;
+    let link_hash_key = quote_identifier(naming.link_hash_key(&link.name));
+    let source_column = link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter()).next()?;
+
+    let mut hub_hks_stg_sql = String::new();
+    for hub_reference in &link.hub_references {
+        let hub_hk_parts_sql = dbt_hash_parts(hub_reference.source_columns.iter(), true);
+        hub_hks_stg_sql.push_str(&format!(",\n    auto_dw.hash(ARRAY_TO_STRING(ARRAY[{}], ',')) AS {}", hub_hk_parts_sql, quote_identifier(naming.hub_hash_key(&hub_reference.business_key_name))));
+    }
+
+    Some(format!(r#"{{{{ config(unique_key={unique_key}) }}}}
+
+WITH
+stg_data AS (
+SELECT DISTINCT ON (1)
+    auto_dw.hash(ARRAY_TO_STRING(ARRAY[{link_hk_parts_sql}], ',')) AS {link_hash_key},
+    (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP(6) AS load_ts,
+    {record_source}::TEXT AS record_source{hub_hks_stg_sql}
+FROM {source} AS stg
+)
+SELECT stg_data.* FROM stg_data
+{DBT_IF_INCREMENTAL}
+WHERE NOT EXISTS (SELECT 1 FROM {{{{ this }}}} AS link WHERE link.{link_hash_key} = stg_data.{link_hash_key})
+{DBT_END_IF}
+"#,
+        unique_key = dbt_string(&naming.link_hash_key(&link.name)),
+        record_source = quote_literal(&source_column.schema_name),
+        source = dbt_source(source_column)))
+}
+
+// Satellite models for the descriptors of a hub or link.  Rows are only added when the hash diff differs from
+// the latest row of the parent hash key, taking the lowest hash diff when a source holds several rows for it.
+// Satellites hash their parent key from their own source table, using the parent's hash parts in that table.
+fn dbt_satellite_models(dv_schema: &DVSchema, parent_hash_key: &str, parent_hk_parts_sqls: &[((String, String), String)], descriptors: &Vec<Descriptor>) -> Vec<(String, String)> {
+    let naming = &dv_schema.naming;
+    let parent_hash_key = quote_identifier(parent_hash_key);
+
+    // Satellites in order of their first descriptor, along with their descriptors.
+    let mut satellites: Vec<(String, Vec<&Descriptor>)> = Vec::new();
+    for descriptor in descriptors.iter().filter(|descriptor| descriptor.descriptor_link.source_column.is_some()) {
+        let satellite_name = naming.descriptor_satellite(descriptor);
+        match satellites.iter_mut().find(|(name, _)| name == &satellite_name) {
+            Some((_, satellite_descriptors)) => satellite_descriptors.push(descriptor),
+            None => satellites.push((satellite_name, vec![descriptor])),
+        }
+    }
+
+    satellites
+        .into_iter()
+        .map(|(satellite_name, satellite_descriptors)| {
+            let sat_hash_diff = quote_identifier(naming.descriptor_satellite_hash_diff(satellite_descriptors[0]));
+            let source_column = satellite_descriptors[0].descriptor_link.source_column.as_ref().unwrap();
+            let parent_hk_parts_sql = parent_hk_parts_sqls
+                .iter()
+                .find(|((schema_name, table_name), _)| schema_name == &source_column.schema_name && table_name == &source_column.table_name)
+                .map(|(_, parts_sql)| parts_sql)
+                .unwrap_or_else(|| error!("Satellite {} is loaded from {}.{}, which holds no key of its parent {}.",
+                    satellite_name, source_column.schema_name, source_column.table_name, parent_hash_key));
+
+            let sat_hd_parts_sql = dbt_hash_parts(satellite_descriptors.iter().filter_map(|descriptor| descriptor.descriptor_link.source_column.as_ref()), false);
+            let sat_columns_sql: String = satellite_descriptors
+                .iter()
+                .map(|descriptor| format!(",\n    {} AS {}",
+                    quote_identifier(&descriptor.descriptor_link.source_column.as_ref().unwrap().column_name),
+                    quote_identifier(&descriptor.descriptor_link.alias)))
+                .collect();
+
+            let satellite_model = format!(r#"WITH
+stg AS (
+SELECT DISTINCT ON (stg.{parent_hash_key}) stg.*
+FROM (
+    SELECT
+        *,
+        auto_dw.hash(ARRAY_TO_STRING(ARRAY[{parent_hk_parts_sql}], ',')) AS {parent_hash_key},
+        auto_dw.hash(ARRAY_TO_STRING(ARRAY[{sat_hd_parts_sql}], ',', '')) AS {sat_hash_diff}
+    FROM {source} AS stg
+) AS stg
+ORDER BY stg.{parent_hash_key}, stg.{sat_hash_diff}
+),
+{DBT_IF_INCREMENTAL}
+latest_sat_data AS (
+SELECT DISTINCT ON (sat.{parent_hash_key})
+    sat.{parent_hash_key},
+    sat.{sat_hash_diff}
+FROM {{{{ this }}}} AS sat
+ORDER BY sat.{parent_hash_key}, sat.load_ts DESC
+),
+{DBT_END_IF}
+new_stg_data AS (
+SELECT stg.*
+FROM stg
+{DBT_IF_INCREMENTAL}
+LEFT JOIN latest_sat_data ON stg.{parent_hash_key} = latest_sat_data.{parent_hash_key}
+WHERE latest_sat_data.{parent_hash_key} IS NULL OR stg.{sat_hash_diff} <> latest_sat_data.{sat_hash_diff}
+{DBT_END_IF}
+)
+SELECT
+    {parent_hash_key},
+    (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS load_ts,
+    {record_source}::TEXT AS record_source,
+    {sat_hash_diff}{sat_columns_sql}
+FROM new_stg_data
+"#,
+                record_source = quote_literal(&source_column.schema_name),
+                source = dbt_source(source_column));
+
+            (satellite_name, satellite_model)
+        })
+        .collect()
+}
+
+// A DW or source table drawn in a diagram, with its columns as (type, name, key).
+struct DiagramTable {
+    name: String,
+    label: String,
+    is_source: bool,
+    is_sensitive: bool,
+    columns: Vec<(String, String, &'static str)>,
+}
+
+// Diagram of a Data Vault build's hubs, links, and satellites, with edges from the source tables they load from.
+// format is either Mermaid, for an erDiagram, or Graphviz, for DOT.
+pub fn dv_diagram(build_id: &String, format: &str) -> String {
+    let dv_schema = dv_load_schema_from_build_id(build_id)
+        .unwrap_or_else(|| error!("Build ID: {} not found or did not build any tables.", build_id));
+
+    if dv_schema.dw_model != DWModel::DataVault {
+        error!("Build ID: {} is a {:?} build.  Diagrams are only drawn for Data Vault builds.", build_id, dv_schema.dw_model);
+    }
+
+    let (diagram_tables, diagram_edges) = get_diagram(&dv_schema);
+
+    match format.to_lowercase().as_str() {
+        "mermaid" => diagram_mermaid(&diagram_tables, &diagram_edges),
+        "graphviz" | "dot" => diagram_graphviz(&dv_schema.dw_schema, &diagram_tables, &diagram_edges),
+        _ => error!("Format \"{}\" is not supported.  Available formats include, Mermaid and Graphviz.", format),
+    }
+}
+
+// Tables along with edges, as (from, to, label), between them.
+fn get_diagram(dv_schema: &DVSchema) -> (Vec<DiagramTable>, Vec<(String, String, String)>) {
+    let naming = &dv_schema.naming;
+
+    let mut diagram_tables: Vec<DiagramTable> = Vec::new();
+    let mut diagram_edges: Vec<(String, String, String)> = Vec::new();
+
+    let add_source_edge = |diagram_tables: &mut Vec<DiagramTable>, diagram_edges: &mut Vec<(String, String, String)>, source_column: &ColumnData, table_name: &str| {
+        let source_name = format!("{}.{}", source_column.schema_name, source_column.table_name);
+        let source_column_entry = (source_column.column_type_name.clone(), source_column.column_name.clone(), "");
+        match diagram_tables.iter_mut().find(|diagram_table| diagram_table.is_source && diagram_table.name == source_name) {
+            Some(diagram_table) => {
+                if !diagram_table.columns.contains(&source_column_entry) {
+                    diagram_table.columns.push(source_column_entry);
+                }
+            }
+            None => diagram_tables.push(DiagramTable {
+                name: source_name.clone(),
+                label: source_name.clone(),
+                is_source: true,
+                is_sensitive: false,
+                columns: vec![source_column_entry],
+            }),
+        }
+        let source_edge = (source_name, table_name.to_string(), "loads".to_string());
+        if !diagram_edges.contains(&source_edge) {
+            diagram_edges.push(source_edge);
+        }
+    };
+
+    let add_satellites = |diagram_tables: &mut Vec<DiagramTable>, diagram_edges: &mut Vec<(String, String, String)>, parent_name: &str, parent_hash_key: &str, descriptors: &Vec<Descriptor>| {
+        for descriptor in descriptors {
+            let satellite_name = naming.descriptor_satellite(descriptor);
+            let column_type_name = descriptor.descriptor_link.source_column.as_ref().map(|source_column| source_column.column_type_name.clone()).unwrap_or_default();
+            let descriptor_column = (column_type_name, descriptor.descriptor_link.alias.clone(), "");
+
+            match diagram_tables.iter_mut().find(|diagram_table| !diagram_table.is_source && diagram_table.name == satellite_name) {
+                Some(diagram_table) => diagram_table.columns.push(descriptor_column),
+                None => {
+                    let label = if descriptor.is_sensitive { format!("{} (sensitive)", satellite_name) } else { satellite_name.clone() };
+                    diagram_tables.push(DiagramTable {
+                        name: satellite_name.clone(),
+                        label,
+                        is_source: false,
+                        is_sensitive: descriptor.is_sensitive,
+                        columns: vec![
+                            ("VARCHAR".to_string(), parent_hash_key.to_string(), "FK"),
+                            ("TIMESTAMP".to_string(), "load_ts".to_string(), ""),
+                            ("VARCHAR".to_string(), "record_source".to_string(), ""),
+                            ("VARCHAR".to_string(), naming.descriptor_satellite_hash_diff(descriptor), ""),
+                            descriptor_column,
+                        ],
+                    });
+                    diagram_edges.push((parent_name.to_string(), satellite_name.clone(), parent_hash_key.to_string()));
+                }
+            }
+
+            if let Some(source_column) = descriptor.descriptor_link.source_column.as_ref() {
+                add_source_edge(diagram_tables, diagram_edges, source_column, &satellite_name);
+            }
+        }
+    };
+
+    for business_key in &dv_schema.business_keys {
+        let hub_name = naming.hub(&business_key.name);
+        let hub_hash_key = naming.hub_hash_key(&business_key.name);
+
+        let mut columns = vec![
+            ("VARCHAR".to_string(), hub_hash_key.clone(), "PK"),
+            ("TIMESTAMP".to_string(), "load_ts".to_string(), ""),
+            ("VARCHAR".to_string(), "record_source".to_string(), ""),
+        ];
+        for part_link in &business_key.business_key_part_links {
+            columns.push(("VARCHAR".to_string(), naming.business_key(&part_link.alias), ""));
+        }
+        diagram_tables.push(DiagramTable { name: hub_name.clone(), label: hub_name.clone(), is_source: false, is_sensitive: false, columns });
+
+        for part_link in &business_key.business_key_part_links {
+            for source_column in &part_link.source_columns {
+                add_source_edge(&mut diagram_tables, &mut diagram_edges, source_column, &hub_name);
+            }
+        }
+
+        add_satellites(&mut diagram_tables, &mut diagram_edges, &hub_name, &hub_hash_key, &business_key.descriptors);
+    }
+
+    for link in &dv_schema.links {
+        let link_name = naming.link(&link.name);
+        let link_hash_key = naming.link_hash_key(&link.name);
+
+        let mut columns = vec![
+            ("VARCHAR".to_string(), link_hash_key.clone(), "PK"),
+            ("TIMESTAMP".to_string(), "load_ts".to_string(), ""),
+            ("VARCHAR".to_string(), "record_source".to_string(), ""),
+        ];
+        for hub_reference in &link.hub_references {
+            let hub_hash_key = naming.hub_hash_key(&hub_reference.business_key_name);
+            columns.push(("VARCHAR".to_string(), hub_hash_key.clone(), "FK"));
+            diagram_edges.push((naming.hub(&hub_reference.business_key_name), link_name.clone(), hub_hash_key));
+        }
+        diagram_tables.push(DiagramTable { name: link_name.clone(), label: link_name.clone(), is_source: false, is_sensitive: false, columns });
+
+        for hub_reference in &link.hub_references {
+            for source_column in &hub_reference.source_columns {
+                add_source_edge(&mut diagram_tables, &mut diagram_edges, source_column, &link_name);
+            }
+        }
+
+        add_satellites(&mut diagram_tables, &mut diagram_edges, &link_name, &link_hash_key, &link.descriptors);
+    }
+
+    (diagram_tables, diagram_edges)
+}
+
+// Mermaid entity and attribute names are words, so other characters are replaced.  Entities are labeled with
+// their actual names.
+fn mermaid_word(value: &str) -> String {
+    let word: String = value.chars().map(|character| if character.is_alphanumeric() || character == '_' || character == '-' { character } else { '_' }).collect();
+    if word.is_empty() { "_".to_string() } else { word }
+}
+
+fn diagram_mermaid(diagram_tables: &Vec<DiagramTable>, diagram_edges: &Vec<(String, String, String)>) -> String {
+    let entity_id = |name: &str, is_source: bool| format!("{}{}", if is_source { "src_" } else { "" }, mermaid_word(name));
+    let is_source = |name: &str| diagram_tables.iter().any(|diagram_table| diagram_table.is_source && diagram_table.name == name);
+
+    let mut mermaid = String::from("erDiagram\n");
+
+    for diagram_table in diagram_tables {
+        mermaid.push_str(&format!("    {}[{}] {{\n", entity_id(&diagram_table.name, diagram_table.is_source), dbt_string(&diagram_table.label)));
+        for (column_type, column_name, key) in &diagram_table.columns {
+            let comment = if mermaid_word(column_name) != *column_name { format!(" {}", dbt_string(column_name)) } else { String::new() };
+            mermaid.push_str(&format!("        {} {}{}{}\n", mermaid_word(column_type), mermaid_word(column_name), if key.is_empty() { String::new() } else { format!(" {}", key) }, comment));
+        }
+        mermaid.push_str("    }\n");
+    }
+
+    for (from, to, label) in diagram_edges {
+        let relationship = if is_source(from) { "||--o{" } else { "||--|{" };
+        mermaid.push_str(&format!("    {} {} {} : {}\n", entity_id(from, is_source(from)), relationship, entity_id(to, false), dbt_string(label)));
+    }
+
+    mermaid
+}
+
+// DOT strings are double quoted with backslash escapes.
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn diagram_graphviz(dw_schema: &str, diagram_tables: &Vec<DiagramTable>, diagram_edges: &Vec<(String, String, String)>) -> String {
+    let node_id = |name: &str, is_source: bool| dot_string(&format!("{}{}", if is_source { "source:" } else { "dw:" }, name));
+    let is_source = |name: &str| diagram_tables.iter().any(|diagram_table| diagram_table.is_source && diagram_table.name == name);
+
+    let mut dot = format!("digraph {} {{\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n", dot_string(dw_schema));
+
+    for diagram_table in diagram_tables {
+        let columns: String = diagram_table.columns
+            .iter()
+            .map(|(column_type, column_name, key)| format!("{}{} {}\\l", if key.is_empty() { String::new() } else { format!("{} ", key) }, column_name, column_type))
+            .collect();
+        let label = format!("{}\n\n", diagram_table.label).replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+            + &columns.replace('"', "\\\"");
+        let style = match (diagram_table.is_source, diagram_table.is_sensitive) {
+            (true, _) => ", shape=cylinder",
+            (false, true) => ", color=red, fontcolor=red",
+            (false, false) => "",
+        };
+        dot.push_str(&format!("    {} [label=\"{}\"{}];\n", node_id(&diagram_table.name, diagram_table.is_source), label, style));
+    }
+
+    for (from, to, label) in diagram_edges {
+        dot.push_str(&format!("    {} -> {} [label={}];\n", node_id(from, is_source(from)), node_id(to, false), dot_string(label)));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
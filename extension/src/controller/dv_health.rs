@@ -0,0 +1,388 @@
+use pgrx::prelude::*;
+use std::collections::HashMap;
+
+use crate::model::queries;
+use crate::model::dv_schema::*;
+
+use super::dv_loader::*;
+
+// Health of a DW table built from a DVSchema.
+pub struct TableHealth {
+    pub table_name: String,
+    pub table_type: String,
+    pub status: String,
+    pub last_load_ts: Option<String>,
+    pub rows_last_load: i64,
+    pub matches_schema: bool,
+    pub pending_transformer_columns: i64,
+    pub recent_errors: i64,
+    pub status_response: String,
+}
+
+// A DW table as described by a DVSchema.
+pub struct DWTable {
+    pub name: String,
+    pub table_type: &'static str,
+    load_ts_column: &'static str,
+    has_record_source: bool,
+    // Expected columns along with their type, when known.
+    pub columns: Vec<(String, Option<String>)>,
+    source_tables: Vec<(String, String)>,
+}
+
+pub fn dv_health(dv_schema: &DVSchema) -> Vec<TableHealth> {
+    get_dw_tables(dv_schema)
+        .into_iter()
+        .map(|dw_table| get_table_health(&dv_schema.dw_schema, &dw_table))
+        .collect()
+}
+
+fn get_table_health(dw_schema: &String, dw_table: &DWTable) -> TableHealth {
+
+    let table_columns = get_table_columns(dw_schema, &dw_table.name);
+    let table_exists = !table_columns.is_empty();
+
+    let mismatched_columns: Vec<String> = dw_table.columns
+        .iter()
+        .filter(|(column_name, column_type_name)| {
+            match (table_columns.get(column_name), column_type_name) {
+                (None, _) => true,
+                (Some(table_column_type_name), Some(column_type_name)) => table_column_type_name != column_type_name,
+                (Some(_), None) => false,
+            }
+        })
+        .map(|(column_name, _)| column_name.clone())
+        .collect();
+
+    let (last_load_ts, rows_last_load) = if table_exists {
+        get_table_load_stats(dw_schema, dw_table)
+    } else {
+        (None, 0)
+    };
+
+    let pending_transformer_columns: i64 = dw_table.source_tables
+        .iter()
+        .map(|(schema_name, table_name)| get_pending_transformer_columns(schema_name, table_name))
+        .sum();
+
+    let (recent_errors, last_error) = get_recent_errors(dw_table);
+
+    let (status, status_response) = if !table_exists {
+        ("Error", "Table not found.".to_string())
+    } else if !mismatched_columns.is_empty() {
+        ("Error", format!("Table does not match its schema, check column(s): {}.", mismatched_columns.join(", ")))
+    } else if recent_errors > 0 {
+        ("Error", format!("{} error(s) over the last day, most recently: {}", recent_errors, last_error.unwrap_or_default()))
+    } else if last_load_ts.is_none() {
+        ("Warning", "Table has not been loaded.".to_string())
+    } else if pending_transformer_columns > 0 {
+        ("Warning", format!("{} source column(s) are awaiting the transformer.", pending_transformer_columns))
+    } else {
+        ("OK", "Healthy.".to_string())
+    };
+
+    TableHealth {
+        table_name: dw_table.name.clone(),
+        table_type: dw_table.table_type.to_string(),
+        status: status.to_string(),
+        last_load_ts,
+        rows_last_load,
+        matches_schema: table_exists && mismatched_columns.is_empty(),
+        pending_transformer_columns,
+        recent_errors,
+        status_response,
+    }
+}
+
+pub fn get_dw_tables(dv_schema: &DVSchema) -> Vec<DWTable> {
+    match dv_schema.dw_model {
+        DWModel::DataVault => get_data_vault_tables(dv_schema),
+        DWModel::Kimball => get_kimball_tables(dv_schema),
+    }
+}
+
+fn get_data_vault_tables(dv_schema: &DVSchema) -> Vec<DWTable> {
+    let naming = &dv_schema.naming;
+    let mut dw_tables: Vec<DWTable> = Vec::new();
+
+    for business_key in &dv_schema.business_keys {
+        let hub_name = naming.hub(&business_key.name);
+        let hub_hash_key = naming.hub_hash_key(&business_key.name);
+
+        let mut hub_columns = vec![(hub_hash_key.clone(), None)];
+        let mut hub_source_tables: Vec<(String, String)> = Vec::new();
+        for part_link in &business_key.business_key_part_links {
+            hub_columns.push((naming.business_key(&part_link.alias), target_column_type(&part_link.target_column)));
+            push_source_tables(&mut hub_source_tables, &part_link.source_columns);
+        }
+
+        dw_tables.push(DWTable {
+            name: hub_name.clone(),
+            table_type: "Hub",
+            load_ts_column: "load_ts",
+            has_record_source: true,
+            columns: hub_columns,
+            source_tables: hub_source_tables,
+        });
+
+        dw_tables.extend(get_satellite_tables(naming, &hub_hash_key, &business_key.descriptors));
+
+        if business_key.status_tracking {
+            dw_tables.push(DWTable {
+                name: naming.status_satellite(&business_key.name),
+                table_type: "Status Satellite",
+                load_ts_column: "load_ts",
+                has_record_source: true,
+                columns: vec![(hub_hash_key.clone(), None), ("status".to_string(), None)],
+                source_tables: Vec::new(),
+            });
+        }
+
+        let mut pit_columns = vec![(hub_hash_key.clone(), None)];
+        for satellite_name in dv_pit_satellite_names(naming, business_key) {
+            pit_columns.push((dv_pit_load_ts_column(&satellite_name), None));
+        }
+
+        dw_tables.push(DWTable {
+            name: naming.pit(&business_key.name),
+            table_type: "PIT",
+            load_ts_column: "snapshot_ts",
+            has_record_source: false,
+            columns: pit_columns,
+            source_tables: Vec::new(),
+        });
+    }
+
+    for link in &dv_schema.links {
+        let link_name = naming.link(&link.name);
+        let link_hash_key = naming.link_hash_key(&link.name);
+
+        let mut link_columns = vec![(link_hash_key.clone(), None)];
+        let mut link_source_tables: Vec<(String, String)> = Vec::new();
+        for hub_reference in &link.hub_references {
+            link_columns.push((naming.hub_hash_key(&hub_reference.business_key_name), target_column_type(&hub_reference.target_column)));
+            push_source_tables(&mut link_source_tables, &hub_reference.source_columns);
+        }
+
+        dw_tables.push(DWTable {
+            name: link_name.clone(),
+            table_type: "Link",
+            load_ts_column: "load_ts",
+            has_record_source: true,
+            columns: link_columns.clone(),
+            source_tables: link_source_tables,
+        });
+
+        dw_tables.extend(get_satellite_tables(naming, &link_hash_key, &link.descriptors));
+
+        dw_tables.push(DWTable {
+            name: naming.bridge(&link.name),
+            table_type: "Bridge",
+            load_ts_column: "snapshot_ts",
+            has_record_source: false,
+            columns: link_columns.into_iter().map(|(column_name, _)| (column_name, None)).collect(),
+            source_tables: Vec::new(),
+        });
+    }
+
+    dw_tables
+}
+
+fn get_satellite_tables(naming: &Naming, parent_hash_key: &String, descriptors: &Vec<Descriptor>) -> Vec<DWTable> {
+    let mut satellite_tables: Vec<DWTable> = Vec::new();
+
+    for descriptor in descriptors {
+        let satellite_name = naming.descriptor_satellite(descriptor);
+
+        let index = match satellite_tables.iter().position(|satellite_table| satellite_table.name == satellite_name) {
+            Some(index) => index,
+            None => {
+                satellite_tables.push(DWTable {
+                    name: satellite_name.clone(),
+                    table_type: if descriptor.is_sensitive { "Satellite - Sensitive" } else { "Satellite" },
+                    load_ts_column: "load_ts",
+                    has_record_source: true,
+                    columns: vec![(parent_hash_key.clone(), None), (naming.descriptor_satellite_hash_diff(descriptor), None)],
+                    source_tables: Vec::new(),
+                });
+                satellite_tables.len() - 1
+            }
+        };
+
+        let satellite_table = &mut satellite_tables[index];
+        satellite_table.columns.push((descriptor.descriptor_link.alias.clone(), descriptor_column_type(descriptor)));
+        if let Some(source_column) = &descriptor.descriptor_link.source_column {
+            push_source_tables(&mut satellite_table.source_tables, std::slice::from_ref(source_column));
+        }
+    }
+
+    satellite_tables
+}
+
+fn get_kimball_tables(dv_schema: &DVSchema) -> Vec<DWTable> {
+    let naming = &dv_schema.naming;
+    let mut dw_tables: Vec<DWTable> = Vec::new();
+
+    for business_key in &dv_schema.business_keys {
+        let mut source_tables: Vec<(String, String)> = Vec::new();
+        for part_link in &business_key.business_key_part_links {
+            push_source_tables(&mut source_tables, &part_link.source_columns);
+        }
+
+        for (dimension_name, descriptors) in dv_dimensions(naming, business_key) {
+            let mut columns = vec![
+                (naming.surrogate_key(&business_key.name, &dimension_name), None),
+                (naming.dimension_hash_key(&business_key.name), None),
+                (naming.hash_diff(&business_key.name, &dimension_name), None),
+            ];
+            for part_link in &business_key.business_key_part_links {
+                columns.push((naming.business_key(&part_link.alias), None));
+            }
+            for descriptor in descriptors {
+                columns.push((descriptor.descriptor_link.alias.clone(), descriptor_column_type(descriptor)));
+            }
+
+            dw_tables.push(DWTable {
+                name: dimension_name,
+                table_type: "Dimension",
+                load_ts_column: "valid_from",
+                has_record_source: true,
+                columns,
+                source_tables: source_tables.clone(),
+            });
+        }
+    }
+
+    for link in &dv_schema.links {
+        let fact_name = naming.fact(&link.name);
+
+        let mut columns = vec![(naming.surrogate_key(&link.name, &fact_name), None), (naming.hash_diff(&link.name, &fact_name), None)];
+        let mut source_tables: Vec<(String, String)> = Vec::new();
+        for hub_reference in &link.hub_references {
+            let dimension_name = naming.dimension(&hub_reference.business_key_name);
+            columns.push((naming.surrogate_key(&hub_reference.business_key_name, &dimension_name), None));
+            push_source_tables(&mut source_tables, &hub_reference.source_columns);
+        }
+        for descriptor in &link.descriptors {
+            columns.push((descriptor.descriptor_link.alias.clone(), descriptor_column_type(descriptor)));
+        }
+
+        dw_tables.push(DWTable {
+            name: fact_name,
+            table_type: "Fact",
+            load_ts_column: "load_ts",
+            has_record_source: true,
+            columns,
+            source_tables,
+        });
+    }
+
+    dw_tables
+}
+
+fn target_column_type(target_column: &Option<ColumnData>) -> Option<String> {
+    target_column.as_ref().map(|target_column| target_column.column_type_name.clone())
+}
+
+// Satellite, dimension, and fact columns take the type of their source column.
+fn descriptor_column_type(descriptor: &Descriptor) -> Option<String> {
+    target_column_type(&descriptor.descriptor_link.target_column)
+        .or_else(|| target_column_type(&descriptor.descriptor_link.source_column))
+}
+
+fn push_source_tables(source_tables: &mut Vec<(String, String)>, source_columns: &[ColumnData]) {
+    for source_column in source_columns {
+        let source_table = (source_column.schema_name.clone(), source_column.table_name.clone());
+        if !source_tables.contains(&source_table) {
+            source_tables.push(source_table);
+        }
+    }
+}
+
+fn get_table_columns(dw_schema: &String, table_name: &String) -> HashMap<String, String> {
+    Spi::connect(|client| {
+        let mut table_columns: HashMap<String, String> = HashMap::new();
+
+        let results = client.select(queries::TABLE_COLUMNS, None,
+            Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), dw_schema.into_datum()),
+                (PgOid::from(pg_sys::TEXTOID), table_name.into_datum()),
+            ]));
+
+        match results {
+            Ok(results) => {
+                for result in results {
+                    let column_name = result.get_datum_by_ordinal(1).unwrap().value::<String>().unwrap().unwrap();
+                    let column_type_name = result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap().unwrap();
+                    table_columns.insert(column_name, column_type_name);
+                }
+            }
+            Err(e) => {
+                log!("Error reading columns of {}.{}: {:?}", dw_schema, table_name, e);
+            }
+        }
+
+        table_columns
+    })
+}
+
+fn get_table_load_stats(dw_schema: &String, dw_table: &DWTable) -> (Option<String>, i64) {
+    let table_load_stats_query = queries::table_load_stats(dw_schema, &dw_table.name, dw_table.load_ts_column, dw_table.has_record_source);
+
+    Spi::connect(|client| {
+        match client.select(&table_load_stats_query, None, None) {
+            Ok(results) => {
+                if let Some(result) = results.into_iter().next() {
+                    let last_load_ts = result.get_datum_by_ordinal(1).unwrap().value::<String>().unwrap();
+                    let rows_last_load = result.get_datum_by_ordinal(2).unwrap().value::<i64>().unwrap().unwrap_or(0);
+                    return (last_load_ts, rows_last_load);
+                }
+                (None, 0)
+            }
+            Err(e) => {
+                log!("Error reading load stats of {}.{}: {:?}", dw_schema, dw_table.name, e);
+                (None, 0)
+            }
+        }
+    })
+}
+
+fn get_pending_transformer_columns(schema_name: &String, table_name: &String) -> i64 {
+    Spi::get_one_with_args::<i64>(queries::PENDING_TRANSFORMER_COLUMNS,
+        vec![
+            (PgOid::from(pg_sys::TEXTOID), schema_name.into_datum()),
+            (PgOid::from(pg_sys::TEXTOID), table_name.into_datum()),
+        ])
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+fn get_recent_errors(dw_table: &DWTable) -> (i64, Option<String>) {
+    let source_schemas: Vec<String> = dw_table.source_tables.iter().map(|(schema_name, _)| schema_name.clone()).collect();
+    let source_tables: Vec<String> = dw_table.source_tables.iter().map(|(schema_name, table_name)| format!("{}.{}", schema_name, table_name)).collect();
+
+    Spi::connect(|client| {
+        let results = client.select(queries::RECENT_ERRORS, None,
+            Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), dw_table.name.clone().into_datum()),
+                (PgOid::from(pg_sys::TEXTARRAYOID), source_schemas.into_datum()),
+                (PgOid::from(pg_sys::TEXTARRAYOID), source_tables.into_datum()),
+            ]));
+
+        match results {
+            Ok(results) => {
+                if let Some(result) = results.into_iter().next() {
+                    let recent_errors = result.get_datum_by_ordinal(1).unwrap().value::<i64>().unwrap().unwrap_or(0);
+                    let last_error = result.get_datum_by_ordinal(2).unwrap().value::<String>().unwrap();
+                    return (recent_errors, last_error);
+                }
+                (0, None)
+            }
+            Err(e) => {
+                log!("Error reading recent errors of {}: {:?}", dw_table.name, e);
+                (0, None)
+            }
+        }
+    })
+}
@@ -1,6 +1,10 @@
 use pgrx::prelude::*;
+use pgrx::spi::{quote_identifier, quote_literal, quote_qualified_identifier};
 use std::collections::HashMap;
 use crate::model::dv_schema::*;
+use crate::utility::guc;
+
+use super::dv_builder::try_execute;
 
 pub fn dv_load_schema_from_build_id(build_id: &String) -> Option<DVSchema> {
     let get_schema_query: &str = r#"
@@ -43,26 +47,763 @@ pub fn dv_load_schema_from_build_id(build_id: &String) -> Option<DVSchema> {
     return schema_result;
 }
 
-// Refreshes based on dv_schema
-pub fn dv_data_loader(dv_schema: &DVSchema) {
+// Latest schema pushed to the repo for dw_schema and not rolled back, along with the build_id that produced it.
+pub fn dv_load_latest_schema(dw_schema: &String) -> Option<(String, DVSchema)> {
+    let get_schema_query: &str = r#"
+        SELECT build_id, schema
+        FROM auto_dw.dv_repo
+        WHERE schema->>'DW Schema' = $1 AND rolled_back_at IS NULL
+        ORDER BY insert_time DESC
+        LIMIT 1
+    "#;
+
+    // Variable to store the result
+    let mut schema_result: Option<(String, DVSchema)> = None;
+
+    Spi::connect( |client| {
+        let results = client.select(get_schema_query, None, 
+            Some(vec![
+                (PgOid::from(pg_sys::TEXTOID), dw_schema.into_datum()),
+            ]));
+
+        match results {
+            Ok(results) => {
+                if let Some(result) = results.into_iter().next() {
+                    let build_id = result.get_datum_by_ordinal(1).unwrap().value::<String>().unwrap().unwrap();
+                    let schema_json = result.get_datum_by_ordinal(2).unwrap().value::<pgrx::Json>().unwrap().unwrap();
+                    let deserialized_schema: Result<DVSchema, serde_json::Error> = serde_json::from_value(schema_json.0);
+                    match deserialized_schema {
+                        Ok(deserialized_schema) => {
+                            schema_result = Some((build_id, deserialized_schema));
+                        },
+                        Err(_) => {
+                            log!("Schema could not be deserialized");
+                        },
+                    }
+                }
+            },
+            Err(_) => {
+                log!("Schema could not deserialized");
+            },
+        }
+
+    });
+    return schema_result;
+}
+
+// Narrows dv_schema to the business keys and links sourced from schema_name.table_name.
+// Only descriptors from that table are kept so other orbits are left untouched.
+pub fn dv_schema_filter_table(dv_schema: &DVSchema, schema_name: &str, table_name: &str) -> DVSchema {
+    dv_schema_filter(dv_schema, |column: &ColumnData| column.schema_name == schema_name && column.table_name == table_name)
+}
+
+// Narrows dv_schema to the business keys and links sourced from tables in schema_name.
+pub fn dv_schema_filter_schema(dv_schema: &DVSchema, schema_name: &str) -> DVSchema {
+    dv_schema_filter(dv_schema, |column: &ColumnData| column.schema_name == schema_name)
+}
+
+fn dv_schema_filter(dv_schema: &DVSchema, is_source_table: impl Fn(&ColumnData) -> bool) -> DVSchema {
+
+    let mut business_keys: Vec<BusinessKey> = Vec::new();
+
+    for business_key in &dv_schema.business_keys {
+
+        let descriptors: Vec<Descriptor> = business_key.descriptors
+            .iter()
+            .filter(|descriptor| descriptor.descriptor_link.source_column.as_ref().map_or(false, &is_source_table))
+            .cloned()
+            .collect();
+
+        let has_source_table_part = business_key.business_key_part_links
+            .iter()
+            .any(|part_link| part_link.source_columns.iter().any(&is_source_table));
+
+        if has_source_table_part || !descriptors.is_empty() {
+            let mut business_key = business_key.clone();
+            business_key.descriptors = descriptors;
+            business_keys.push(business_key);
+        }
+    }
+
+    let links: Vec<Link> = dv_schema.links
+        .iter()
+        .filter(|link| link.hub_references.iter().any(|hub_reference| hub_reference.source_columns.iter().any(&is_source_table)))
+        .cloned()
+        .collect();
+
+    DVSchema {
+        id: dv_schema.id,
+        dw_schema: dv_schema.dw_schema.clone(),
+        create_timestamp_gmt: dv_schema.create_timestamp_gmt,
+        modified_timestamp_gmt: dv_schema.modified_timestamp_gmt,
+        business_keys,
+        links,
+        dw_model: dv_schema.dw_model,
+        naming: dv_schema.naming.clone(),
+    }
+}
+
+// Refreshes based on dv_schema, returning the DML that was run or the error it raised.
+pub fn dv_data_loader(dv_schema: &DVSchema) -> Result<String, String> {
+
+    let dv_dml = dv_data_loader_dml(dv_schema);
+
+    try_execute(&dv_dml)?;
+    log!("Data Pushed to DV tables.");
+
+    Ok(dv_dml)
+}
+
+// Refreshes the PIT and bridge tables of dv_schema, returning the DML that was run or the error it raised.
+// dv_schema should be the full schema, as each PIT row covers all of a hub's satellites.
+pub fn dv_pit_bridge_refresh(dv_schema: &DVSchema) -> Result<String, String> {
+
+    let pit_bridge_dml = dv_pit_bridge_refresh_dml(dv_schema);
+
+    if pit_bridge_dml.is_empty() {
+        return Ok(pit_bridge_dml);
+    }
+
+    try_execute(&pit_bridge_dml)?;
+    log!("PIT and bridge tables refreshed.");
+
+    Ok(pit_bridge_dml)
+}
+
+// Satellite table names, keyed by the hub hash key, tracked by a hub's PIT table.
+pub fn dv_pit_satellite_names(naming: &Naming, business_key: &BusinessKey) -> Vec<String> {
+
+    let mut satellite_names: Vec<String> = Vec::new();
+
+    for descriptor in &business_key.descriptors {
+        let satellite_name = naming.descriptor_satellite(descriptor);
+        if !satellite_names.contains(&satellite_name) {
+            satellite_names.push(satellite_name);
+        }
+    }
+
+    if business_key.status_tracking {
+        satellite_names.push(naming.status_satellite(&business_key.name));
+    }
+
+    satellite_names
+}
+
+// Satellites of the hubs a link references, paired with the hub hash key they hang off, in hub reference order.
+pub fn dv_bridge_satellite_names(dv_schema: &DVSchema, link: &Link) -> Vec<(String, String)> {
+
+    let naming = &dv_schema.naming;
+    let mut satellite_names: Vec<(String, String)> = Vec::new();
+
+    for hub_reference in &link.hub_references {
+        let business_key = match dv_schema.business_keys.iter().find(|business_key| business_key.name == hub_reference.business_key_name) {
+            Some(business_key) => business_key,
+            None => continue,
+        };
+        let hub_hash_key = naming.hub_hash_key(&business_key.name);
+        for satellite_name in dv_pit_satellite_names(naming, business_key) {
+            if !satellite_names.iter().any(|(_, name)| *name == satellite_name) {
+                satellite_names.push((hub_hash_key.clone(), satellite_name));
+            }
+        }
+    }
+
+    satellite_names
+}
+
+// PIT column holding the latest load_ts of a satellite.
+pub fn dv_pit_load_ts_column(satellite_name: &str) -> String {
+    format!("{}_load_ts", satellite_name)
+}
+
+// stg columns hashed to a hub key, in business key part order.
+fn dv_stg_hash_parts(source_columns: &[&ColumnData]) -> String {
+    source_columns
+        .iter()
+        .map(|source_column| format!("stg.{}::TEXT", quote_identifier(&source_column.column_name)))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Snapshots are taken every pg_auto_dw.pit_snapshot_interval since the latest snapshot, or now for empty tables.
+pub fn dv_pit_bridge_refresh_dml(dv_schema: &DVSchema) -> String {
+
+    let mut pit_bridge_dmls = String::new();
+    let dw_schema_name = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
+    // PIT and bridge tables are only built for Data Vault.
+    if dv_schema.dw_model != DWModel::DataVault {
+        return pit_bridge_dmls;
+    }
+
+    let snapshot_interval = quote_literal(guc::get_guc(guc::PgAutoDWGuc::PitSnapshotInterval).unwrap_or_else(|| "1 day".to_string()));
+    let snapshot_now = "(CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE";
+
+    for business_key in &dv_schema.business_keys {
+
+        let pit_table = quote_qualified_identifier(dw_schema_name, &naming.pit(&business_key.name));
+        let hub_table = quote_qualified_identifier(dw_schema_name, &naming.hub(&business_key.name));
+        let hub_hash_key = quote_identifier(naming.hub_hash_key(&business_key.name));
+
+        let mut pit_sat_cols = String::new();
+        let mut pit_sat_load_ts_sql = String::new();
+        for satellite_name in dv_pit_satellite_names(naming, business_key) {
+            let satellite_table = quote_qualified_identifier(dw_schema_name, &satellite_name);
+            let load_ts_column = quote_identifier(dv_pit_load_ts_column(&satellite_name));
+            pit_sat_cols.push_str(&format!(r#",
+                {load_ts_column}"#));
+            pit_sat_load_ts_sql.push_str(&format!(r#",
+                (
+                SELECT MAX(sat.load_ts)
+                FROM {satellite_table} AS sat
+                WHERE sat.{hub_hash_key} = hub.{hub_hash_key} AND
+                    sat.load_ts <= snapshots.snapshot_ts
+                ) AS {load_ts_column}"#));
+        }
+
+        let pit_insert_sql = format!(r#"
+            -- PIT INSERT SQL
+            INSERT INTO {pit_table} (
+                {hub_hash_key},
+                snapshot_ts{pit_sat_cols}
+            )
+            WITH snapshots AS (
+            SELECT generate_series(
+                COALESCE((SELECT MAX(snapshot_ts) FROM {pit_table}) + {snapshot_interval}::INTERVAL, {snapshot_now}),
+                {snapshot_now},
+                {snapshot_interval}::INTERVAL
+            ) AS snapshot_ts
+            )
+            SELECT
+                hub.{hub_hash_key},
+                snapshots.snapshot_ts{pit_sat_load_ts_sql}
+            FROM {hub_table} AS hub
+            CROSS JOIN snapshots
+            WHERE hub.load_ts <= snapshots.snapshot_ts AND
+                hub.record_source <> 'SYSTEM'
+            ;
+            "#);
+
+        pit_bridge_dmls.push_str(&pit_insert_sql);
+    }
+
+    for link in &dv_schema.links {
+
+        let bridge_table = quote_qualified_identifier(dw_schema_name, &naming.bridge(&link.name));
+        let link_table = quote_qualified_identifier(dw_schema_name, &naming.link(&link.name));
+        let link_hash_key = quote_identifier(naming.link_hash_key(&link.name));
+
+        let mut bridge_hub_hks_sql = String::new();
+        let mut link_hub_hks_sql = String::new();
+        for hub_reference in &link.hub_references {
+            let hub_hash_key = quote_identifier(naming.hub_hash_key(&hub_reference.business_key_name));
+            bridge_hub_hks_sql.push_str(&format!(r#",
+                {hub_hash_key}"#));
+            link_hub_hks_sql.push_str(&format!(r#",
+                link.{hub_hash_key}"#));
+        }
+
+        // As in the PIT, the latest load_ts of each referenced hub's satellites as of the snapshot.
+        let mut bridge_sat_cols = String::new();
+        let mut bridge_sat_load_ts_sql = String::new();
+        for (hub_hash_key, satellite_name) in dv_bridge_satellite_names(dv_schema, link) {
+            let satellite_table = quote_qualified_identifier(dw_schema_name, &satellite_name);
+            let hub_hash_key = quote_identifier(&hub_hash_key);
+            let load_ts_column = quote_identifier(dv_pit_load_ts_column(&satellite_name));
+            bridge_sat_cols.push_str(&format!(r#",
+                {load_ts_column}"#));
+            bridge_sat_load_ts_sql.push_str(&format!(r#",
+                (
+                SELECT MAX(sat.load_ts)
+                FROM {satellite_table} AS sat
+                WHERE sat.{hub_hash_key} = link.{hub_hash_key} AND
+                    sat.load_ts <= snapshots.snapshot_ts
+                ) AS {load_ts_column}"#));
+        }
+
+        let bridge_insert_sql = format!(r#"
+            -- BRIDGE INSERT SQL
+            INSERT INTO {bridge_table} (
+                {link_hash_key},
+                snapshot_ts{bridge_hub_hks_sql}{bridge_sat_cols}
+            )
+            WITH snapshots AS (
+            SELECT generate_series(
+                COALESCE((SELECT MAX(snapshot_ts) FROM {bridge_table}) + {snapshot_interval}::INTERVAL, {snapshot_now}),
+                {snapshot_now},
+                {snapshot_interval}::INTERVAL
+            ) AS snapshot_ts
+            )
+            SELECT
+                link.{link_hash_key},
+                snapshots.snapshot_ts{link_hub_hks_sql}{bridge_sat_load_ts_sql}
+            FROM {link_table} AS link
+            CROSS JOIN snapshots
+            WHERE link.load_ts <= snapshots.snapshot_ts
+            ;
+            "#);
+
+        pit_bridge_dmls.push_str(&bridge_insert_sql);
+    }
+
+    pit_bridge_dmls
+}
+
+// Hub and satellite DML for every business key in dv_schema.
+pub fn dv_data_loader_dml(dv_schema: &DVSchema) -> String {
+
+    if dv_schema.dw_model == DWModel::Kimball {
+        return dv_data_loader_dim_dml(dv_schema) + &dv_data_loader_fact_dml(dv_schema);
+    }
 
     // Create SQL For Hubs
     let hub_dml = dv_data_loader_hub_dml(dv_schema);
 
+    // Create SQL For Links
+    let link_dml = dv_data_loader_link_dml(dv_schema);
+
     // Create SQL For Satellites 
     let sat_dml = dv_data_loader_sat_dml(dv_schema);
 
-    // Run SQL
-    let dv_dml = hub_dml + &sat_dml;
-    
-    // Build Tables using DDL
-    Spi::connect( |mut client| {
-        // client.select(dv_objects_query, None, None);
-        _ = client.update(&dv_dml, None, None);
-        log!("Data Pushed to DV tables.");
+    // Create SQL For Status Satellites
+    let status_sat_dml = dv_data_loader_status_sat_dml(dv_schema);
+
+    hub_dml + &link_dml + &sat_dml + &status_sat_dml
+}
+
+// Kimball dimension names for a business key along with their descriptors.
+pub fn dv_dimensions<'a>(naming: &Naming, business_key: &'a BusinessKey) -> Vec<(String, Vec<&'a Descriptor>)> {
+
+    let mut dimensions: Vec<(String, Vec<&Descriptor>)> = Vec::new();
+
+    let (sensitive_descriptors, descriptors): (Vec<&Descriptor>, Vec<&Descriptor>) = business_key.descriptors
+        .iter()
+        .partition(|descriptor| descriptor.is_sensitive);
+
+    dimensions.push((naming.dimension(&business_key.name), descriptors));
+
+    if !sensitive_descriptors.is_empty() {
+        dimensions.push((naming.sensitive_dimension(&business_key.name), sensitive_descriptors));
     }
-    );
 
+    dimensions
+}
+
+// SCD2 loads.  The current row of a business key is expired and a new row inserted when its descriptors change.
+fn dv_data_loader_dim_dml (dv_schema: &DVSchema) -> String {
+
+    let mut dim_dmls = String::new();
+    let dw_schema_name = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
+    for business_key in &dv_schema.business_keys {
+
+        let business_key_hash_key = quote_identifier(naming.dimension_hash_key(&business_key.name));
+
+        // Business keys from every source table of the business key, as the hub loads them.
+        let mut dim_keys_sqls: Vec<String> = Vec::new();
+        for (source_schema, source_table) in business_key.source_tables() {
+            let source_columns = match business_key.source_columns_of(&source_schema, &source_table) {
+                Some(source_columns) => source_columns,
+                None => continue,
+            };
+            let mut dim_bk_parts_stg_names = String::new();
+            for (part_link, source_column) in business_key.business_key_part_links.iter().zip(&source_columns) {
+                dim_bk_parts_stg_names.push_str(&format!(r#",
+                    stg.{}::TEXT AS {}"#, quote_identifier(&source_column.column_name), quote_identifier(naming.business_key(&part_link.alias))));
+            }
+            dim_keys_sqls.push(format!(r#"
+                SELECT
+                    auto_dw.hash(
+                        ARRAY_TO_STRING(ARRAY[{}], ',')
+                    ) AS {},
+                    {}::TEXT AS record_source{}
+                FROM {} AS stg"#,
+                dv_stg_hash_parts(&source_columns), business_key_hash_key, quote_literal(&source_schema), dim_bk_parts_stg_names, quote_qualified_identifier(&source_schema, &source_table)));
+        }
+        if dim_keys_sqls.is_empty() {
+            continue;
+        }
+        let dim_keys_sql = dim_keys_sqls.join(r#"
+                UNION"#);
+
+        // Arrary Parts
+        let mut dim_bk_parts_sql = String::new();
+        let mut dim_bk_parts_keys_names = String::new();
+        let mut dim_bk_neg_1_init_parts_sql = String::new();
+        for part_link in &business_key.business_key_part_links {
+            let business_key_column = quote_identifier(naming.business_key(&part_link.alias));
+            dim_bk_parts_sql.push_str(&format!(r#",
+                {}"#, business_key_column));
+            dim_bk_parts_keys_names.push_str(&format!(r#",
+                keys.{}"#, business_key_column));
+            dim_bk_neg_1_init_parts_sql.push_str(&format!(r#",
+                '-1'::TEXT AS {}"#, business_key_column));
+        }
+
+        for (dimension_name, descriptors) in dv_dimensions(naming, business_key) {
+
+            let dimension_table = quote_qualified_identifier(dw_schema_name, &dimension_name);
+            let dimension_surrogate_key = quote_identifier(naming.surrogate_key(&business_key.name, &dimension_name));
+            let dimension_hash_diff = quote_identifier(naming.hash_diff(&business_key.name, &dimension_name));
+
+            // Descriptors are grouped by source table, one row per business key each, and joined to the keys on the
+            // business key hash.
+            let mut desc_source_tables: Vec<((String, String), Vec<&Descriptor>)> = Vec::new();
+            for descriptor in &descriptors {
+                let source_column = match descriptor.descriptor_link.source_column.as_ref() {
+                    Some(source_column) => source_column,
+                    None => continue,
+                };
+                let source_table = (source_column.schema_name.clone(), source_column.table_name.clone());
+                match desc_source_tables.iter_mut().find(|(desc_source_table, _)| *desc_source_table == source_table) {
+                    Some((_, table_descriptors)) => table_descriptors.push(descriptor),
+                    None => desc_source_tables.push((source_table, vec![descriptor])),
+                }
+            }
+
+            let mut dim_desc_ctes = String::new();
+            let mut dim_desc_joins = String::new();
+            let mut dim_desc_sql_stg_array = String::new();
+            let mut dim_desc_stg_names = String::new();
+            let mut dim_desc_sql = String::new();
+            let mut dim_desc_columns: HashMap<String, String> = HashMap::new();
+            for (desc_index, ((source_schema, source_table), table_descriptors)) in desc_source_tables.iter().enumerate() {
+                // Only tables holding every part of the business key can be joined to it.
+                let source_columns = match business_key.source_columns_of(source_schema, source_table) {
+                    Some(source_columns) => source_columns,
+                    None => {
+                        warning!("Descriptors of dimension {} from {}.{} are not loaded, as the table does not hold its business key.", dimension_name, source_schema, source_table);
+                        continue;
+                    }
+                };
+                let desc_cte = quote_identifier(format!("desc_{}", desc_index));
+
+                let mut desc_stg_names = String::new();
+                let mut desc_sql_array = String::new();
+                for descriptor in table_descriptors {
+                    let source_column = descriptor.descriptor_link.source_column.as_ref().unwrap();
+                    let alias = quote_identifier(&descriptor.descriptor_link.alias);
+                    desc_stg_names.push_str(&format!(r#",
+                    stg.{} AS {}"#, quote_identifier(&source_column.column_name), alias));
+                    desc_sql_array.push_str(&format!(r#"{}.{}::TEXT,"#, desc_cte, alias));
+                }
+                desc_sql_array.pop(); // Removing the last ","
+
+                dim_desc_ctes.push_str(&format!(r#"
+            {desc_cte} AS (
+            SELECT DISTINCT ON ({desc_cte}.{business_key_hash_key}) {desc_cte}.*
+            FROM (
+                SELECT
+                    auto_dw.hash(
+                        ARRAY_TO_STRING(ARRAY[{hash_parts}], ',')
+                    ) AS {business_key_hash_key}{desc_stg_names}
+                FROM {source_table_sql} AS stg
+            ) AS {desc_cte}
+            ORDER BY {desc_cte}.{business_key_hash_key}, ARRAY_TO_STRING(ARRAY[{desc_sql_array}], ',', '')
+            ),"#,
+                    hash_parts = dv_stg_hash_parts(&source_columns),
+                    source_table_sql = quote_qualified_identifier(source_schema, source_table)));
+                dim_desc_joins.push_str(&format!(r#"
+            LEFT JOIN {desc_cte} ON
+                {desc_cte}.{business_key_hash_key} = keys.{business_key_hash_key}"#));
+
+                for descriptor in table_descriptors {
+                    let alias = quote_identifier(&descriptor.descriptor_link.alias);
+                    let desc_column = format!("{}.{}", desc_cte, alias);
+                    dim_desc_stg_names.push_str(&format!(r#",
+                {}"#, desc_column));
+                    dim_desc_sql.push_str(&format!(r#",
+                {}"#, alias));
+                    dim_desc_columns.insert(descriptor.descriptor_link.alias.clone(), desc_column);
+                }
+            }
+
+            // Hashed in descriptor order, whichever table each descriptor is loaded from.
+            for descriptor in &descriptors {
+                if let Some(desc_column) = dim_desc_columns.get(&descriptor.descriptor_link.alias) {
+                    dim_desc_sql_stg_array.push_str(&format!(r#"{}::TEXT,"#, desc_column));
+                }
+            }
+            dim_desc_sql_stg_array.pop(); // Removing the last ","
+
+            let dim_hd_sql = if dim_desc_sql_stg_array.is_empty() {
+                "auto_dw.hash('')".to_string()
+            } else {
+                format!("auto_dw.hash(ARRAY_TO_STRING(ARRAY[{}], ',', ''))", dim_desc_sql_stg_array)
+            };
+
+            let dim_sql = format!(r#"
+            -- DIM INIT SQL
+            INSERT INTO {dimension_table} (
+                {dimension_surrogate_key},
+                {business_key_hash_key},
+                valid_from,
+                valid_to,
+                is_current,
+                record_source,
+                {dimension_hash_diff}{dim_bk_parts_sql}
+            )
+            SELECT
+                -1 AS {dimension_surrogate_key},
+                auto_dw.hash(ARRAY_TO_STRING(ARRAY[-1], ',')::TEXT) AS {business_key_hash_key},
+                '0001-01-01'::TIMESTAMP WITHOUT TIME ZONE AS valid_from,
+                '9999-12-31'::TIMESTAMP WITHOUT TIME ZONE AS valid_to,
+                TRUE AS is_current,
+                'SYSTEM'::TEXT AS record_source,
+                auto_dw.hash('') AS {dimension_hash_diff}{dim_bk_neg_1_init_parts_sql}
+            WHERE NOT EXISTS (SELECT 1 FROM {dimension_table} WHERE {dimension_surrogate_key} = -1)
+            ;
+
+            -- DIM SCD2 SQL
+            WITH keys AS (
+            SELECT DISTINCT ON (keys.{business_key_hash_key}) keys.*
+            FROM ({dim_keys_sql}
+            ) AS keys
+            ORDER BY keys.{business_key_hash_key}, keys.record_source
+            ),{dim_desc_ctes}
+            stg AS (
+            SELECT
+                keys.{business_key_hash_key},
+                {dim_hd_sql} AS {dimension_hash_diff},
+                keys.record_source{dim_bk_parts_keys_names}{dim_desc_stg_names}
+            FROM keys{dim_desc_joins}
+            ),
+            changed_stg_data AS (
+            SELECT stg.*
+                FROM stg
+            LEFT JOIN {dimension_table} AS dim ON
+                stg.{business_key_hash_key} = dim.{business_key_hash_key} AND
+                dim.is_current
+            WHERE dim.{business_key_hash_key} IS NULL OR
+                stg.{dimension_hash_diff} <> dim.{dimension_hash_diff}
+            ),
+            expired AS (
+            UPDATE {dimension_table} AS dim
+            SET
+                valid_to = (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE,
+                is_current = FALSE
+            FROM changed_stg_data
+            WHERE dim.{business_key_hash_key} = changed_stg_data.{business_key_hash_key} AND
+                dim.is_current
+            )
+            INSERT INTO {dimension_table} (
+                {business_key_hash_key},
+                valid_from,
+                valid_to,
+                is_current,
+                record_source,
+                {dimension_hash_diff}{dim_bk_parts_sql}{dim_desc_sql}
+            )
+            SELECT
+                {business_key_hash_key},
+                (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS valid_from,
+                '9999-12-31'::TIMESTAMP WITHOUT TIME ZONE AS valid_to,
+                TRUE AS is_current,
+                record_source,
+                {dimension_hash_diff}{dim_bk_parts_sql}{dim_desc_sql}
+            FROM changed_stg_data
+            ;
+            "#);
+
+            dim_dmls.push_str(&dim_sql);
+        }
+    }
+
+    dim_dmls
+}
+
+// Fact loads.  Each distinct source row is inserted once, referencing the current dimension rows, or the
+// unknown (-1) dimension row when a reference has no match.
+fn dv_data_loader_fact_dml (dv_schema: &DVSchema) -> String {
+
+    let mut fact_dmls = String::new();
+    let dw_schema_name = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
+    for link in &dv_schema.links {
+
+        let fact_name = naming.fact(&link.name);
+        let fact_table = quote_qualified_identifier(dw_schema_name, &fact_name);
+        let fact_hash_diff = quote_identifier(naming.hash_diff(&link.name, &fact_name));
+
+        // Facts are loaded from a single source table.
+        let (source_schema, source_table) = match link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter()).next() {
+            Some(source_column) => (source_column.schema_name.clone(), source_column.table_name.clone()),
+            None => continue,
+        };
+        let source_table_sql = quote_qualified_identifier(&source_schema, &source_table);
+        let record_source = quote_literal(&source_schema);
+
+        let mut fact_hd_parts_sql_stg_array = String::new();
+        let mut dim_hks_stg_sql = String::new();
+        let mut dim_sks_sql = String::new();
+        let mut dim_sks_select_sql = String::new();
+        let mut dim_joins_sql = String::new();
+
+        // Dimensions are aliased by their position, as their names need not be valid aliases.
+        for (dimension_index, hub_reference) in link.hub_references.iter().enumerate() {
+            let dimension_name = naming.dimension(&hub_reference.business_key_name);
+            let dimension_table = quote_qualified_identifier(dw_schema_name, &dimension_name);
+            let dimension_alias = format!("dim_{}", dimension_index);
+            let dimension_hash_key = quote_identifier(naming.dimension_hash_key(&hub_reference.business_key_name));
+            let dimension_surrogate_key = quote_identifier(naming.surrogate_key(&hub_reference.business_key_name, &dimension_name));
+
+            let mut dim_hk_parts_sql_stg_array = String::new();
+            for source_column in &hub_reference.source_columns {
+                dim_hk_parts_sql_stg_array.push_str(&format!(r#"COALESCE(stg.{}::TEXT, '-1'),"#, quote_identifier(&source_column.column_name)));
+            }
+            fact_hd_parts_sql_stg_array.push_str(&dim_hk_parts_sql_stg_array);
+            dim_hk_parts_sql_stg_array.pop(); // Removing the last ","
+
+            dim_hks_stg_sql.push_str(&format!(r#",
+                auto_dw.hash(
+                    ARRAY_TO_STRING(ARRAY[{dim_hk_parts_sql_stg_array}], ',')
+                ) AS {dimension_hash_key}"#));
+            dim_sks_sql.push_str(&format!(r#",
+                {dimension_surrogate_key}"#));
+            dim_sks_select_sql.push_str(&format!(r#",
+                COALESCE({dimension_alias}.{dimension_surrogate_key}, -1) AS {dimension_surrogate_key}"#));
+            dim_joins_sql.push_str(&format!(r#"
+            LEFT JOIN {dimension_table} AS {dimension_alias} ON
+                stg.{dimension_hash_key} = {dimension_alias}.{dimension_hash_key} AND
+                {dimension_alias}.is_current"#));
+        }
+
+        let mut fact_desc_stg_names = String::new();
+        let mut fact_desc_sql = String::new();
+        let mut fact_desc_select_sql = String::new();
+        for descriptor in &link.descriptors {
+            if let Some(source_column) = descriptor.descriptor_link.source_column.as_ref() {
+                let source_column_name = quote_identifier(&source_column.column_name);
+                let alias = quote_identifier(&descriptor.descriptor_link.alias);
+                fact_hd_parts_sql_stg_array.push_str(&format!(r#"stg.{}::TEXT,"#, source_column_name));
+                fact_desc_stg_names.push_str(&format!(r#",
+                stg.{} AS {}"#, source_column_name, alias));
+                fact_desc_sql.push_str(&format!(r#",
+                {}"#, alias));
+                fact_desc_select_sql.push_str(&format!(r#",
+                stg.{}"#, alias));
+            }
+        }
+        fact_hd_parts_sql_stg_array.pop(); // Removing the last ","
+
+        // Identical source rows are repeated transactions, so each is hashed with its occurrence among them.  Reloads
+        // hash the same rows alike, and only rows beyond those already loaded add facts.
+
+        let fact_sql = format!(r#"
+            -- FACT INSERT SQL
+            INSERT INTO {fact_table} (
+                {fact_hash_diff},
+                load_ts,
+                record_source{dim_sks_sql}{fact_desc_sql}
+            )
+            WITH stg AS (
+            SELECT
+                auto_dw.hash(
+                    ARRAY_TO_STRING(ARRAY[{fact_hd_parts_sql_stg_array}], ',', '') || ',' ||
+                    ROW_NUMBER() OVER (PARTITION BY ARRAY_TO_STRING(ARRAY[{fact_hd_parts_sql_stg_array}], ',', ''))
+                ) AS {fact_hash_diff}{dim_hks_stg_sql}{fact_desc_stg_names}
+            FROM {source_table_sql} AS stg
+            )
+            SELECT
+                stg.{fact_hash_diff},
+                (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS load_ts,
+                {record_source} AS record_source{dim_sks_select_sql}{fact_desc_select_sql}
+            FROM stg{dim_joins_sql}
+            LEFT JOIN {fact_table} AS fact ON
+                stg.{fact_hash_diff} = fact.{fact_hash_diff}
+            WHERE fact.{fact_hash_diff} IS NULL
+            ;
+            "#);
+
+        fact_dmls.push_str(&fact_sql);
+    }
+
+    fact_dmls
+}
+
+fn dv_data_loader_link_dml (dv_schema: &DVSchema) -> String {
+
+    let mut link_insert_dmls = String::new();
+    let dw_schema_name = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
+    for link in &dv_schema.links {
+
+        let link_table = quote_qualified_identifier(dw_schema_name, &naming.link(&link.name));
+        let link_hash_key = quote_identifier(naming.link_hash_key(&link.name));
+
+        // Links are loaded from a single source table.
+        let (source_schema, source_table) = match link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter()).next() {
+            Some(source_column) => (source_column.schema_name.clone(), source_column.table_name.clone()),
+            None => continue,
+        };
+        let source_table_sql = quote_qualified_identifier(&source_schema, &source_table);
+        let record_source = quote_literal(&source_schema);
+
+        // Unknown references hash to the hub's '-1' record.
+        let mut link_hk_parts_sql_stg_array = String::new();
+        let mut hub_hks_stg_sql = String::new();
+        let mut hub_hks_sql = String::new();
+
+        for hub_reference in &link.hub_references {
+            let mut hub_hk_parts_sql_stg_array = String::new();
+            for source_column in &hub_reference.source_columns {
+                let e = format!(r#"COALESCE(stg.{}::TEXT, '-1'),"#, quote_identifier(&source_column.column_name));
+                hub_hk_parts_sql_stg_array.push_str(&e);
+            }
+            link_hk_parts_sql_stg_array.push_str(&hub_hk_parts_sql_stg_array);
+            hub_hk_parts_sql_stg_array.pop(); // Removing the last ","
+
+            let hub_hash_key = quote_identifier(naming.hub_hash_key(&hub_reference.business_key_name));
+
+            let e = format!(r#",
+                auto_dw.hash(
+                            ARRAY_TO_STRING(ARRAY[{}], ',')
+                        ) AS {}"#, hub_hk_parts_sql_stg_array, hub_hash_key);
+            hub_hks_stg_sql.push_str(&e);
+
+            let r = format!(r#",
+            {}"#, hub_hash_key);
+            hub_hks_sql.push_str(&r);
+        }
+        link_hk_parts_sql_stg_array.pop(); // Removing the last ","
+
+        let link_insert_sql = format!(r#"
+            INSERT INTO {link_table} (
+                {link_hash_key},
+                load_ts,
+                record_source{hub_hks_sql}
+            )
+            WITH
+            stg_data AS (
+            SELECT DISTINCT
+                auto_dw.hash(
+                            ARRAY_TO_STRING(ARRAY[{link_hk_parts_sql_stg_array}], ',')
+                        ) AS {link_hash_key},
+                (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP(6) AS load_ts,
+                {record_source} AS record_source{hub_hks_stg_sql}
+            FROM {source_table_sql} AS stg
+            ),
+            new_stg_data AS (
+            SELECT stg_data.* FROM stg_data
+            LEFT JOIN {link_table} AS link ON stg_data.{link_hash_key} = link.{link_hash_key}
+            WHERE link.{link_hash_key} IS NULL
+            )
+            SELECT
+            {link_hash_key},
+            load_ts,
+            record_source{hub_hks_sql}
+            FROM new_stg_data
+            ;
+            "#);
+
+        link_insert_dmls.push_str(&link_insert_sql);
+    }
+
+    link_insert_dmls
 }
 
 fn dv_data_loader_hub_dml (dv_schema: &DVSchema) -> String {
@@ -73,26 +814,28 @@ fn dv_data_loader_hub_dml (dv_schema: &DVSchema) -> String {
 
         // Hub Buildout
         let dw_schema_name = &dv_schema.dw_schema;
-        let busines_key_name = &business_key.name;
+        let naming = &dv_schema.naming;
+        let hub_table = quote_qualified_identifier(dw_schema_name, &naming.hub(&business_key.name));
+        let hub_hash_key = quote_identifier(naming.hub_hash_key(&business_key.name));
 
         // Business Key Part(s)
         let mut hub_bk_parts_sql = String::new();
         for part_link in &business_key.business_key_part_links {
             let r = format!(r#",
-                            {}_bk"#, part_link.alias);
+                            {}"#, quote_identifier(naming.business_key(&part_link.alias)));
             hub_bk_parts_sql.push_str(&r);
         }
 
         // INSERT INTO Header
         let hub_insert_into_header_part_sql = format!(r#"
-            INSERT INTO {}.hub_{} (
-                hub_{}_hk,
+            INSERT INTO {} (
+                {},
                 load_ts,
                 record_source
                 {}
             )
             "#, 
-            dw_schema_name, busines_key_name, busines_key_name, hub_bk_parts_sql);
+            hub_table, hub_hash_key, hub_bk_parts_sql);
 
 
         // Business Key Part(s) Init SQL
@@ -100,10 +843,10 @@ fn dv_data_loader_hub_dml (dv_schema: &DVSchema) -> String {
         let mut hub_bk_neg_2_init_parts_sql = String::new();
         for part_link in &business_key.business_key_part_links {
             let neg_1: String = format!(r#",
-                '-1'::TEXT AS {}_bk"#, part_link.alias);
+                '-1'::TEXT AS {}"#, quote_identifier(naming.business_key(&part_link.alias)));
             hub_bk_neg_1_init_parts_sql.push_str(&neg_1);
             let neg_2: String = format!(r#",
-                '-2'::TEXT AS {}_bk"#, part_link.alias);
+                '-2'::TEXT AS {}"#, quote_identifier(naming.business_key(&part_link.alias)));
             hub_bk_neg_2_init_parts_sql.push_str(&neg_2);
         }
                 
@@ -114,94 +857,175 @@ fn dv_data_loader_hub_dml (dv_schema: &DVSchema) -> String {
                 WHEN COUNT(*) > 0 THEN TRUE
                 ELSE FALSE
             END is_initialized
-            FROM {}.hub_{}
+            FROM {}
             )
             SELECT
-                auto_dw.hash(ARRAY_TO_STRING(ARRAY[-1], ',')::TEXT) AS hub_{}_hk,
+                auto_dw.hash(ARRAY_TO_STRING(ARRAY[-1], ',')::TEXT) AS {},
                 '0001-01-01'::TIMESTAMP WITHOUT TIME ZONE AS load_ts, 
                 'SYSTEM'::TEXT AS record_source
                 {}
                 FROM initialized WHERE NOT initialized.is_initialized
             UNION
             SELECT
-                auto_dw.hash(ARRAY_TO_STRING(ARRAY[-2], ',')::TEXT) AS hub_{}_hk,
+                auto_dw.hash(ARRAY_TO_STRING(ARRAY[-2], ',')::TEXT) AS {},
                 '0001-01-01'::TIMESTAMP WITHOUT TIME ZONE AS load_ts,
                 'SYSTEM'::TEXT AS record_source
                 {}
                 FROM initialized WHERE NOT initialized.is_initialized
             ;
-            "#, dw_schema_name, busines_key_name, 
-            busines_key_name, hub_bk_neg_1_init_parts_sql, 
-            busines_key_name, hub_bk_neg_2_init_parts_sql);
+            "#, hub_table, 
+            hub_hash_key, hub_bk_neg_1_init_parts_sql, 
+            hub_hash_key, hub_bk_neg_2_init_parts_sql);
 
         let hub_insert_init = hub_insert_into_header_part_sql.clone() + &hub_insert_into_init_part_sql;
         hub_insert_dmls.push_str(&hub_insert_init);
 
-        // Insert Main
+        // Insert Main - One insert per source table, as a business key may be sourced from several tables.
+        for (source_schema, source_table) in business_key.source_tables() {
 
-        // Arrary Parts
-        let mut hub_bk_parts_sql_stg_array = String ::new();
-        for part_link in &business_key.business_key_part_links {
-            // TODO: Need acount for more than once source.  However, Vec data structure isn't ideal - refactor. 
-            let e = format!(r#"stg.{}::TEXT,"#, part_link.source_columns[0].column_name);
-            hub_bk_parts_sql_stg_array.push_str(&e);
-        } 
-        hub_bk_parts_sql_stg_array.pop(); // Removing the last ","
+            let source_columns = match business_key.source_columns_of(&source_schema, &source_table) {
+                Some(source_columns) => source_columns,
+                None => continue, // Not every business key part is sourced from this table.
+            };
 
-        // Source Schema
-        let mut source_schema = String::new();
-        let mut source_table = String::new();
+            // Arrary Parts
+            let hub_bk_parts_sql_stg_array = dv_stg_hash_parts(&source_columns);
 
-        // Business Key Part(s)
-        let mut hub_bk_parts_stg_names = String::new();
-        for part_link in &business_key.business_key_part_links {
-            let source_column_name = &part_link.source_columns[0].column_name;
-            let e = format!(r#",
-                            stg.{}::TEXT AS {}_bk"#, source_column_name, source_column_name);
-            hub_bk_parts_stg_names.push_str(&e);
-            source_schema = part_link.source_columns[0].schema_name.clone();
-            source_table = part_link.source_columns[0].table_name.clone();
-        }
+            // Business Key Part(s)
+            let mut hub_bk_parts_stg_names = String::new();
+            for (part_link, source_column) in business_key.business_key_part_links.iter().zip(&source_columns) {
+                let e = format!(r#",
+                            stg.{}::TEXT AS {}"#, quote_identifier(&source_column.column_name), quote_identifier(naming.business_key(&part_link.alias)));
+                hub_bk_parts_stg_names.push_str(&e);
+            }
 
-        let hub_insert_into_main_part_sql = format!(r#"
+            let hub_insert_into_main_part_sql = format!(r#"
             WITH
             stg_data AS (
-            SELECT
+            SELECT DISTINCT
                 auto_dw.hash(
                             ARRAY_TO_STRING(ARRAY[{}], ',')
-                        ) AS hub_{}_hk,
+                        ) AS {},
                 (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP(6) AS load_ts,
-                '{}' AS record_source{}
-            FROM {}.{} AS stg
+                {} AS record_source{}
+            FROM {} AS stg
             ),
             new_stg_data AS (
             SELECT stg_data.* FROM stg_data
-            LEFT JOIN {}.hub_{} ON stg_data.hub_{}_hk = hub_{}.hub_{}_hk
-            WHERE hub_{}.hub_{}_hk IS NULL
+            LEFT JOIN {} AS hub ON stg_data.{} = hub.{}
+            WHERE hub.{} IS NULL
             )
             SELECT
-            hub_{}_hk,
+            {},
             load_ts,
             record_source{}
             FROM new_stg_data
             ;
             "#, 
-            hub_bk_parts_sql_stg_array, busines_key_name,
-            source_schema, hub_bk_parts_stg_names,
-            source_schema, source_table,
-            dw_schema_name, busines_key_name, busines_key_name, busines_key_name, busines_key_name,
-            busines_key_name, busines_key_name,
-            busines_key_name,
-            hub_bk_parts_sql
-        );
-
-        let hub_insert_main = hub_insert_into_header_part_sql + &hub_insert_into_main_part_sql;
-        hub_insert_dmls.push_str(&hub_insert_main);
+                hub_bk_parts_sql_stg_array, hub_hash_key,
+                quote_literal(&source_schema), hub_bk_parts_stg_names,
+                quote_qualified_identifier(&source_schema, &source_table),
+                hub_table, hub_hash_key, hub_hash_key,
+                hub_hash_key,
+                hub_hash_key,
+                hub_bk_parts_sql
+            );
+
+            let hub_insert_main = hub_insert_into_header_part_sql.clone() + &hub_insert_into_main_part_sql;
+            hub_insert_dmls.push_str(&hub_insert_main);
+        }
     }
 
     hub_insert_dmls
 }
 
+// Compares the source's business keys to the hub's keys, recording 'deleted' for keys missing from the source
+// and 'reappeared' for keys that return after being deleted.
+fn dv_data_loader_status_sat_dml (dv_schema: &DVSchema) -> String {
+
+    let mut status_sat_insert_dmls = String::new();
+    let dw_schema_name = &dv_schema.dw_schema;
+    let naming = &dv_schema.naming;
+
+    for business_key in &dv_schema.business_keys {
+
+        if !business_key.status_tracking {
+            continue;
+        }
+
+        let hub_table = quote_qualified_identifier(dw_schema_name, &naming.hub(&business_key.name));
+        let hub_hash_key = quote_identifier(naming.hub_hash_key(&business_key.name));
+        let status_satellite_table = quote_qualified_identifier(dw_schema_name, &naming.status_satellite(&business_key.name));
+
+        // Keys of every source table, as a key is only deleted once it is missing from all of them.  Reappeared keys
+        // are recorded against a source they were observed in, and deleted keys against the source of their hub row.
+        let mut stg_sql_parts: Vec<String> = Vec::new();
+        for (source_schema, source_table) in business_key.source_tables() {
+            if let Some(source_columns) = business_key.source_columns_of(&source_schema, &source_table) {
+                stg_sql_parts.push(format!(r#"
+            SELECT
+                auto_dw.hash(
+                            ARRAY_TO_STRING(ARRAY[{}], ',')
+                        ) AS {},
+                {}::TEXT AS record_source
+            FROM {} AS stg"#, dv_stg_hash_parts(&source_columns), hub_hash_key, quote_literal(&source_schema), quote_qualified_identifier(&source_schema, &source_table)));
+            }
+        }
+        if stg_sql_parts.is_empty() {
+            continue;
+        }
+        let stg_sql = stg_sql_parts.join("\n            UNION");
+
+        let status_sat_insert_sql = format!(r#"
+            -- STATUS SAT INSERT SQL
+            INSERT INTO {status_satellite_table} (
+                {hub_hash_key},
+                load_ts,
+                record_source,
+                status
+            )
+            WITH stg AS (
+            SELECT DISTINCT ON (stg.{hub_hash_key}) stg.*
+            FROM ({stg_sql}
+            ) AS stg
+            ORDER BY stg.{hub_hash_key}, stg.record_source
+            ),
+            latest_status AS (
+            SELECT DISTINCT ON (status_sat.{hub_hash_key})
+                status_sat.{hub_hash_key},
+                status_sat.status
+                FROM {status_satellite_table} AS status_sat
+            ORDER BY status_sat.{hub_hash_key}, status_sat.load_ts DESC
+            ),
+            hub_status AS (
+            SELECT
+                hub.{hub_hash_key},
+                CASE
+                    WHEN stg.{hub_hash_key} IS NULL AND COALESCE(latest_status.status, '') <> 'deleted' THEN 'deleted'
+                    WHEN stg.{hub_hash_key} IS NOT NULL AND latest_status.status = 'deleted' THEN 'reappeared'
+                END AS status,
+                COALESCE(stg.record_source, hub.record_source) AS record_source
+            FROM {hub_table} AS hub
+            LEFT JOIN stg ON hub.{hub_hash_key} = stg.{hub_hash_key}
+            LEFT JOIN latest_status ON hub.{hub_hash_key} = latest_status.{hub_hash_key}
+            WHERE hub.record_source <> 'SYSTEM'
+            )
+            SELECT
+            {hub_hash_key},
+            (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS load_ts,
+            record_source,
+            status
+            FROM hub_status
+            WHERE status IS NOT NULL
+            ;
+            "#);
+
+        status_sat_insert_dmls.push_str(&status_sat_insert_sql);
+    }
+
+    status_sat_insert_dmls
+}
+
 fn dv_data_loader_sat_dml (dv_schema: &DVSchema) -> String {
 
     let mut sat_insert_dmls = String::new();
@@ -209,153 +1033,213 @@ fn dv_data_loader_sat_dml (dv_schema: &DVSchema) -> String {
 
     for business_key in &dv_schema.business_keys {
 
+        // Arrary Parts - Each satellite hashes the hub key from the business key part columns of its own source table.
+        let hub_bk_parts_sql_stg_arrays: Vec<((String, String), String)> = business_key.source_tables()
+            .into_iter()
+            .filter_map(|(source_schema, source_table)| {
+                let source_columns = business_key.source_columns_of(&source_schema, &source_table)?;
+                Some(((source_schema, source_table), dv_stg_hash_parts(&source_columns)))
+            })
+            .collect();
+
+        let parent_hash_key = dv_schema.naming.hub_hash_key(&business_key.name);
+        sat_insert_dmls.push_str(&dv_data_loader_sat_dml_parent(&dv_schema.naming, &dw_schema, &parent_hash_key, &hub_bk_parts_sql_stg_arrays, &business_key.descriptors));
+    }
+
+    for link in &dv_schema.links {
+
         // Arrary Parts
-        let mut hub_bk_parts_sql_stg_array = String ::new();
-        for part_link in &business_key.business_key_part_links {
-            // TODO: Need acount for more than once source.  However, Vec data structure isn't ideal - refactor. 
-            let e = format!(r#"stg.{}::TEXT,"#, part_link.source_columns[0].column_name);
-            hub_bk_parts_sql_stg_array.push_str(&e);
-        } 
-        hub_bk_parts_sql_stg_array.pop(); // Removing the last ","
+        let mut link_hk_parts_sql_stg_array = String::new();
+        for hub_reference in &link.hub_references {
+            for source_column in &hub_reference.source_columns {
+                let e = format!(r#"COALESCE(stg.{}::TEXT, '-1'),"#, quote_identifier(&source_column.column_name));
+                link_hk_parts_sql_stg_array.push_str(&e);
+            }
+        }
+        link_hk_parts_sql_stg_array.pop(); // Removing the last ","
 
-          // Sat Buildout
-        let mut sat_insert_sql_header_parts: HashMap<String, String> = HashMap::new();
-        let mut descriptors_for_sats: HashMap<String, Vec<&Descriptor>> = HashMap::new();
+        let source_table = match link.hub_references.iter().flat_map(|hub_reference| hub_reference.source_columns.iter()).next() {
+            Some(source_column) => (source_column.schema_name.clone(), source_column.table_name.clone()),
+            None => continue,
+        };
 
-        for descriptor in &business_key.descriptors {
+        let parent_hash_key = dv_schema.naming.link_hash_key(&link.name);
+        sat_insert_dmls.push_str(&dv_data_loader_sat_dml_parent(&dv_schema.naming, &dw_schema, &parent_hash_key, &[(source_table, link_hk_parts_sql_stg_array)], &link.descriptors));
+    }
 
-            let sensitive_string = {
-                if descriptor.is_sensitive == true {
-                    "_sensitive".to_string()
-                } else {
-                    "".to_string()
-                }
-            };
+    sat_insert_dmls
+}
 
-            let satellite_sql_key = descriptor.orbit.clone() + &sensitive_string;
+// Satellite DML for the descriptors of a hub or link, parent_hash_key being the hub or link hash key column.
+// parent_hk_parts_sql_stg_arrays holds, per source table, the stg columns hashed to the parent hash key.
+fn dv_data_loader_sat_dml_parent (naming: &Naming, dw_schema: &String, parent_hash_key: &String, parent_hk_parts_sql_stg_arrays: &[((String, String), String)], descriptors: &Vec<Descriptor>) -> String {
 
-            descriptors_for_sats
-                .entry(satellite_sql_key.clone())
-                .or_insert_with(Vec::new)
-                .push(&descriptor);
+    let mut sat_insert_dmls = String::new();
+    let parent_hash_key = quote_identifier(parent_hash_key);
+
+    // Sat Buildout
+    let mut sat_insert_sql_header_parts: HashMap<String, String> = HashMap::new();
+    let mut descriptors_for_sats: HashMap<String, Vec<&Descriptor>> = HashMap::new();
+
+    for descriptor in descriptors {
+
+        // Satellites are keyed by table name, which the naming template keeps unique per orbit.
+        let satellite_sql_key = naming.descriptor_satellite(descriptor);
+
+        descriptors_for_sats
+            .entry(satellite_sql_key.clone())
+            .or_insert_with(Vec::new)
+            .push(&descriptor);
 
-            let desc_column_name = &descriptor.descriptor_link.alias;
+        let desc_column_name = quote_identifier(&descriptor.descriptor_link.alias);
 
-            // SAT INSERT Header 
-            let sat_descriptor_sql_part: String = format!(",\n    {}", desc_column_name);
-            if let Some(existing_sat_sql) = sat_insert_sql_header_parts.get_mut(&satellite_sql_key) {
-                if let Some(pos) = existing_sat_sql.find(")") {
-                    existing_sat_sql.insert_str(pos, &sat_descriptor_sql_part);
+        // SAT INSERT Header - Descriptor columns are collected first, as identifiers may contain any character.
+        let sat_descriptor_sql_part: String = format!(",\n    {}", desc_column_name);
+        sat_insert_sql_header_parts
+            .entry(satellite_sql_key.clone())
+            .or_insert_with(String::new)
+            .push_str(&sat_descriptor_sql_part);
+    }
+
+    let sat_insert_sql_header_parts: HashMap<String, String> = sat_insert_sql_header_parts
+        .into_iter()
+        .map(|(key, sat_descriptors_sql)| {
+            let descriptor = descriptors_for_sats[&key][0];
+            let begin_sat_sql = 
+                format!(r#"
+                        INSERT INTO {} (
+                            {},
+                            load_ts,
+                            record_source,
+                            {}{})
+                        "#, 
+                        quote_qualified_identifier(dw_schema, &key), 
+                        parent_hash_key, 
+                        quote_identifier(naming.descriptor_satellite_hash_diff(descriptor)), sat_descriptors_sql);
+            (key, begin_sat_sql)
+        })
+        .collect();
+
+    // Array SQL
+    let mut sats_source_sql_array: HashMap<String, String> = HashMap::new();
+    for (key, descriptors) in descriptors_for_sats.clone() {
+        let array_part_str = sats_source_sql_array.entry(key.clone()).or_insert_with(String::new);
+    
+        for descriptor in descriptors {
+            if let Some(column) = descriptor.descriptor_link.source_column.as_ref() {
+                let array_part = if array_part_str.is_empty() {
+                    format!("stg.{}::TEXT", quote_identifier(&column.column_name))
                 } else {
-                    println!("The substring \")\" was not found in the original string.");
-                }
-            } else {
-                let begin_sat_sql = 
-                    format!(r#"
-                            INSERT INTO {}.sat_{} (
-                                hub_{}_hk,
-                                load_ts,
-                                record_source,
-                                sat_{}_hd{})
-                            "#, 
-                            dw_schema, &satellite_sql_key, 
-                            business_key.name, 
-                            &satellite_sql_key, sat_descriptor_sql_part);
-
-                sat_insert_sql_header_parts.insert(satellite_sql_key.clone(), begin_sat_sql);
+                    format!(", stg.{}::TEXT", quote_identifier(&column.column_name))
+                };
+                array_part_str.push_str(&array_part);
             }
         }
+    }
 
-        // Array SQL
-        let mut sats_source_sql_array: HashMap<String, String> = HashMap::new();
-        for (key, descriptors) in descriptors_for_sats.clone() {
-            let array_part_str = sats_source_sql_array.entry(key.clone()).or_insert_with(String::new);
-        
-            for descriptor in descriptors {
-                if let Some(column) = descriptor.descriptor_link.source_column.as_ref() {
-                    let array_part = if array_part_str.is_empty() {
-                        format!("stg.{}::TEXT", column.column_name)
-                    } else {
-                        format!(", stg.{}::TEXT", column.column_name)
-                    };
-                    array_part_str.push_str(&array_part);
-                }
+    // Column SQL
+    let mut sats_source_sql_cols: HashMap<String, String> = HashMap::new();
+    for (key, descriptors) in descriptors_for_sats.clone() {
+        let col_part_str = sats_source_sql_cols.entry(key.clone()).or_insert_with(String::new);
+    
+        for descriptor in descriptors {
+            if let Some(column) = descriptor.descriptor_link.source_column.as_ref() {
+                let col_part = format!(r#",
+                                                {}"#, 
+                                                quote_identifier(&column.column_name));
+                col_part_str.push_str(&col_part);
             }
         }
+    }
+
+    // Main Insert
+
+    for (key, insert_header) in sat_insert_sql_header_parts {
 
-        // Column SQL
-        let mut sats_source_sql_cols: HashMap<String, String> = HashMap::new();
-        for (key, descriptors) in descriptors_for_sats.clone() {
-            let col_part_str = sats_source_sql_cols.entry(key.clone()).or_insert_with(String::new);
+        let sat_hash_diff = descriptors_for_sats
+            .get(&key)
+            .and_then(|v| v.get(0))
+            .map(|descriptor| quote_identifier(naming.descriptor_satellite_hash_diff(descriptor)))
+            .unwrap_or_default();
         
-            for descriptor in descriptors {
-                if let Some(column) = descriptor.descriptor_link.source_column.as_ref() {
-                    let col_part = format!(r#",
-                                                    {}"#, 
-                                                    column.column_name);
-                    col_part_str.push_str(&col_part);
-                }
+        let sat_source_sql_array = sats_source_sql_array.get(&key).map(|v| v.as_str()).unwrap_or("NA");
+        let sat_source_sql_cols = sats_source_sql_cols.get(&key).map(|v| v.as_str()).unwrap_or("NA");
+
+        // TODO: Change data structure to support multiple source schemas.
+        let source_schema_name = descriptors_for_sats
+            .get(&key)
+            .and_then(|v| v.get(0))  // Safely get the first element
+            .and_then(|descriptor| descriptor.descriptor_link.source_column.as_ref())  // Safely access target_column
+            .map(|source_column| source_column.schema_name.clone())  // Safely get schema_name and clone it
+            .unwrap_or_default();  // Provide a default value in case of None
+        
+        let source_table_name = descriptors_for_sats
+            .get(&key)
+            .and_then(|v| v.get(0))  // Safely get the first element
+            .and_then(|descriptor| descriptor.descriptor_link.source_column.as_ref())  // Safely access target_column
+            .map(|source_column| source_column.table_name.clone())  // Safely get schema_name and clone it
+            .unwrap_or_default();  // Provide a default value in case of None
+
+        // Descriptors are only validated against the parent's source tables when a schema is applied.
+        let parent_hk_parts_sql_stg_array = match parent_hk_parts_sql_stg_arrays.iter().find(|((schema_name, table_name), _)| *schema_name == source_schema_name && *table_name == source_table_name) {
+            Some((_, parent_hk_parts_sql_stg_array)) => parent_hk_parts_sql_stg_array,
+            None => {
+                warning!("Satellite {} skipped, {}.{} is not a source table of its parent.", key, source_schema_name, source_table_name);
+                continue;
             }
-        }
+        };
 
-        // Main Insert
-
-        for (key, insert_header) in sat_insert_sql_header_parts {
-            
-            let sat_source_sql_array = sats_source_sql_array.get(&key).map(|v| v.as_str()).unwrap_or("NA");
-            let sat_source_sql_cols = sats_source_sql_cols.get(&key).map(|v| v.as_str()).unwrap_or("NA");
-
-            // TODO: Change data structure to support multiple source schemas.
-            let source_schema_name = descriptors_for_sats
-                .get(&key)
-                .and_then(|v| v.get(0))  // Safely get the first element
-                .and_then(|descriptor| descriptor.descriptor_link.source_column.as_ref())  // Safely access target_column
-                .map(|source_column| source_column.schema_name.clone())  // Safely get schema_name and clone it
-                .unwrap_or_default();  // Provide a default value in case of None
-            
-            let source_table_name = descriptors_for_sats
-                .get(&key)
-                .and_then(|v| v.get(0))  // Safely get the first element
-                .and_then(|descriptor| descriptor.descriptor_link.source_column.as_ref())  // Safely access target_column
-                .map(|source_column| source_column.table_name.clone())  // Safely get schema_name and clone it
-                .unwrap_or_default();  // Provide a default value in case of None
-
-            let business_key_name = &business_key.name;
-
-            let insert_sql =  format!(r#"
-                -- SAT INSERT SQL
-                {insert_header}
-                WITH stg AS (
+        let source_table = quote_qualified_identifier(&source_schema_name, &source_table_name);
+        let sat_table = quote_qualified_identifier(dw_schema, &key);
+        let record_source = quote_literal(&source_schema_name);
+
+        // Only payloads that differ from the latest row per parent hash key are inserted.  A source holding several
+        // rows for a parent hash key loads the one with the lowest hash diff, so repeated loads agree on the latest row.
+        // NULL descriptors hash as empty strings, so moving a value between descriptors is a change.
+        let insert_sql =  format!(r#"
+            -- SAT INSERT SQL
+            {insert_header}
+            WITH stg AS (
+            SELECT DISTINCT ON (stg.{parent_hash_key}) stg.*
+            FROM (
                 SELECT 
                     *,
                     auto_dw.hash(
-                        ARRAY_TO_STRING(ARRAY[{hub_bk_parts_sql_stg_array}], ',')
-                    ) AS hub_{business_key_name}_hk,
+                        ARRAY_TO_STRING(ARRAY[{parent_hk_parts_sql_stg_array}], ',')
+                    ) AS {parent_hash_key},
                     auto_dw.hash(
-                        ARRAY_TO_STRING(ARRAY[{sat_source_sql_array}], ',')
-                    ) AS sat_{key}_hd
-                    FROM {source_schema_name}.{source_table_name} AS stg
-                ),
-                new_stg_data AS (  
-                SELECT stg.*
-                    FROM stg
-                LEFT JOIN {dw_schema}.sat_{key} ON 
-                    stg.hub_{business_key_name}_hk = sat_{key}.hub_{business_key_name}_hk AND
-                    stg.sat_{key}_hd = sat_{key}.sat_{key}_hd
-                WHERE sat_{key}.hub_{business_key_name}_hk IS NULL
-                )
-                SELECT   
-                hub_{business_key_name}_hk,
-                (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS load_ts ,
-                '{source_schema_name}' AS record_source ,
-                sat_{key}_hd
-                {sat_source_sql_cols}
-                FROM new_stg_data
-                ; 
-                "#);
-
-            sat_insert_dmls.push_str(&insert_sql);
-        }
+                        ARRAY_TO_STRING(ARRAY[{sat_source_sql_array}], ',', '')
+                    ) AS {sat_hash_diff}
+                    FROM {source_table} AS stg
+            ) AS stg
+            ORDER BY stg.{parent_hash_key}, stg.{sat_hash_diff}
+            ),
+            latest_sat_data AS (
+            SELECT DISTINCT ON (sat.{parent_hash_key})
+                sat.{parent_hash_key},
+                sat.{sat_hash_diff}
+                FROM {sat_table} AS sat
+            ORDER BY sat.{parent_hash_key}, sat.load_ts DESC
+            ),
+            new_stg_data AS (  
+            SELECT stg.*
+                FROM stg
+            LEFT JOIN latest_sat_data ON 
+                stg.{parent_hash_key} = latest_sat_data.{parent_hash_key}
+            WHERE latest_sat_data.{parent_hash_key} IS NULL OR
+                stg.{sat_hash_diff} <> latest_sat_data.{sat_hash_diff}
+            )
+            SELECT   
+            {parent_hash_key},
+            (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP WITHOUT TIME ZONE AS load_ts ,
+            {record_source} AS record_source ,
+            {sat_hash_diff}
+            {sat_source_sql_cols}
+            FROM new_stg_data
+            ; 
+            "#);
+
+        sat_insert_dmls.push_str(&insert_sql);
     }
 
     sat_insert_dmls
@@ -1,5 +1,10 @@
 pub mod bgw_init;
 pub mod bgw_source_objects;
 pub mod bgw_transformer_client;
+pub mod bgw_scheduled_loads;
 pub mod dv_builder;
-pub mod dv_loader;
\ No newline at end of file
+pub mod dv_loader;
+pub mod dv_health;
+pub mod dv_builds;
+pub mod dv_export;
+pub mod dv_apply;
@@ -15,27 +15,201 @@ use model::queries;
 
 #[pg_extern(name="go")]
 fn go_default() -> String {
-    let accepted_transformer_confidence_level: String = 
-        utility::guc::get_guc(guc::PgAutoDWGuc::AcceptedTransformerConfidenceLevel)
-            .unwrap_or_else(|| {
-                error!("GUC: Unable to obtain parameter \"pg_auto_dw.accepted_transformer_confidence_level.\"");
-            });
     let build_id = Uuid::new_v4();
     let message = format!("Build ID: {} | Data warehouse tables are currently being built.", build_id);
     info!("{}", message);
     let build_id = build_id.to_string();
-    let build_flag = "Build";
-    let build_status = "RTD";
-    let status = "Ready to Deploy";
-    let query_insert = &queries::insert_into_build_call(
-        &build_id, &build_flag, &build_status, &status, &accepted_transformer_confidence_level);
-    _ = Spi::run(query_insert);
-    let query_build_pull = &queries::build_object_pull(&build_id);
-    controller::dv_builder::build_dv(&build_id, query_build_pull);
+    build_call(&build_id, "Build", "RTD");
+    controller::dv_builder::build_dv(&build_id);
+
+    message
+}
+
+#[pg_extern(name="go")]
+fn go_flag(flag: &str, status: default!(&str, "'RTD'")) -> TableIterator<
+    'static,
+    (
+        name!(build_id, String),
+        name!(step, String),
+        name!(statement, String),
+        name!(status, String),
+        name!(message, String)
+    )
+> {
+    let build_id = Uuid::new_v4().to_string();
+
+    match flag.to_lowercase().as_str() {
+        "build" => {
+            let message = format!("Build ID: {} | Data warehouse tables are currently being built.", build_id);
+            info!("{}", message);
+            build_call(&build_id, "Build", status);
+            controller::dv_builder::build_dv(&build_id);
+            TableIterator::new(vec![(build_id, "Build".to_string(), String::new(), "Succeeded".to_string(), message)])
+        }
+        "dryrun" => {
+            info!("Build ID: {} | Dry run, data warehouse changes will be rolled back.", build_id);
+            let planned_statements = controller::dv_builder::dry_run_dv(&build_id, status);
+            let rows: Vec<_> = planned_statements
+                .into_iter()
+                .map(|planned_statement| {
+                    let (status, message) = match planned_statement.error {
+                        Some(error) => ("Failed".to_string(), error),
+                        None => ("Succeeded".to_string(), "Rolled back.".to_string()),
+                    };
+                    (build_id.clone(), planned_statement.step, planned_statement.statement, status, message)
+                })
+                .collect();
+            TableIterator::new(rows)
+        }
+        "push-table" => {
+            // Tables are parsed as SQL names, so quoted names may hold dots and keep their case.
+            let table_name_parts = Spi::get_one_with_args::<Vec<String>>("SELECT parse_ident($1)", vec![
+                (PgOid::from(pg_sys::TEXTOID), status.into_datum()),
+            ]).ok().flatten().unwrap_or_default();
+            let (schema_name, table_name) = match table_name_parts.as_slice() {
+                [schema_name, table_name] => (schema_name.as_str(), table_name.as_str()),
+                _ => error!("Push-Table expects a table in the form \"schema.table\", received \"{}\".", status),
+            };
+            let dw_schema = guc::get_guc(guc::PgAutoDWGuc::DwSchema).expect("DW SCHEMA GUC is not set.");
+            let (repo_build_id, dv_schema) = controller::dv_loader::dv_load_latest_schema(&dw_schema)
+                .unwrap_or_else(|| error!("No data warehouse build found for DW schema \"{}\".", dw_schema));
+            let table_dv_schema = controller::dv_loader::dv_schema_filter_table(&dv_schema, schema_name, table_name);
+            // Tables that only feed a link are pushed as well.
+            if table_dv_schema.business_keys.is_empty() && table_dv_schema.links.is_empty() {
+                error!("Table \"{}\" is not part of Build ID: {}.", status, repo_build_id);
+            }
+            // The load and the PIT and bridge refresh succeed or fail together.
+            let dv_dml = controller::dv_loader::dv_data_loader_dml(&table_dv_schema) + &controller::dv_loader::dv_pit_bridge_refresh_dml(&dv_schema);
+            let (push_status, message) = match controller::dv_builder::try_execute(&dv_dml) {
+                Ok(()) => ("Succeeded", format!("Build ID: {} | Data pushed from table {} for {} business key(s) and {} link(s).", repo_build_id, status, table_dv_schema.business_keys.len(), table_dv_schema.links.len())),
+                Err(e) => ("Failed", format!("Build ID: {} | Data could not be pushed from table {}: {}", repo_build_id, status, e)),
+            };
+            info!("{}", message);
+            TableIterator::new(vec![(repo_build_id, "Push-Table".to_string(), dv_dml, push_status.to_string(), message)])
+        }
+        "rollback" => {
+            let rollback_build_id = status.to_string();
+            let rollback_sql = controller::dv_builds::dv_rollback(&rollback_build_id);
+            let message = format!("Build ID: {} | Build rolled back.", rollback_build_id);
+            info!("{}", message);
+            TableIterator::new(vec![(rollback_build_id, "Rollback".to_string(), rollback_sql, "Succeeded".to_string(), message)])
+        }
+        _ => error!("Flag \"{}\" is not supported.  Available flags include, Build, DryRun, Push-Table, and Rollback.", flag),
+    }
+}
+
+#[pg_extern]
+fn builds() -> TableIterator<
+    'static,
+    (
+        name!(build_id, String),
+        name!(build_ts, String),
+        name!(dw_schema, Option<String>),
+        name!(status, String),
+        name!(tables_created, Vec<String>),
+        name!(tables_altered, Vec<String>)
+    )
+> {
+    let rows: Vec<_> = controller::dv_builds::dv_builds()
+        .into_iter()
+        .map(|build| (
+            build.build_id,
+            build.build_ts,
+            build.dw_schema,
+            build.status,
+            build.tables_created,
+            build.tables_altered,
+        ))
+        .collect();
+    TableIterator::new(rows)
+}
+
+#[pg_extern]
+fn schema_diff(build_id_a: &str, build_id_b: &str) -> TableIterator<
+    'static,
+    (
+        name!(change_type, String),
+        name!(object_type, String),
+        name!(parent, Option<String>),
+        name!(name, String),
+        name!(detail, String)
+    )
+> {
+    let rows: Vec<_> = controller::dv_builds::dv_schema_diff(&build_id_a.to_string(), &build_id_b.to_string())
+        .into_iter()
+        .map(|schema_change| (
+            schema_change.change_type,
+            schema_change.object_type,
+            schema_change.parent,
+            schema_change.name,
+            schema_change.detail,
+        ))
+        .collect();
+    TableIterator::new(rows)
+}
+
+#[pg_extern]
+fn export_sql(build_id: &str) -> String {
+    controller::dv_export::dv_export_sql(&build_id.to_string())
+}
+
+#[pg_extern]
+fn export_dbt(build_id: &str) -> TableIterator<
+    'static,
+    (
+        name!(path, String),
+        name!(content, String)
+    )
+> {
+    TableIterator::new(controller::dv_export::dv_export_dbt(&build_id.to_string()))
+}
 
+#[pg_extern]
+fn diagram(build_id: &str, format: default!(&str, "'Mermaid'")) -> String {
+    controller::dv_export::dv_diagram(&build_id.to_string(), format)
+}
+
+#[pg_extern]
+fn apply_schema(schema: pgrx::JsonB) -> String {
+    let build_id = controller::dv_apply::dv_apply_schema(schema.0);
+    let message = format!("Build ID: {} | Schema applied, data warehouse tables are currently being built.", build_id);
+    info!("{}", message);
     message
 }
 
+#[pg_extern]
+fn refresh_pit_bridge() -> String {
+    let dw_schema = guc::get_guc(guc::PgAutoDWGuc::DwSchema).expect("DW SCHEMA GUC is not set.");
+    let (build_id, dv_schema) = controller::dv_loader::dv_load_latest_schema(&dw_schema)
+        .unwrap_or_else(|| error!("No data warehouse build found for DW schema \"{}\".", dw_schema));
+    if let Err(e) = controller::dv_loader::dv_pit_bridge_refresh(&dv_schema) {
+        error!("Build ID: {} | PIT and bridge tables could not be refreshed: {}", build_id, e);
+    }
+    let message = format!("Build ID: {} | PIT and bridge tables refreshed.", build_id);
+    info!("{}", message);
+    message
+}
+
+// Records the columns with the given status against the build, to be pulled with queries::BUILD_OBJECT_PULL.
+fn build_call(build_id: &str, build_flag: &str, status_code: &str) {
+    let accepted_transformer_confidence_level: String = 
+        utility::guc::get_guc(guc::PgAutoDWGuc::AcceptedTransformerConfidenceLevel)
+            .unwrap_or_else(|| {
+                error!("GUC: Unable to obtain parameter \"pg_auto_dw.accepted_transformer_confidence_level.\"");
+            });
+    let (build_status, status) = match status_code.to_uppercase().as_str() {
+        "RTD" => ("RTD", "Ready to Deploy"),
+        _ => error!("Status \"{}\" is not supported.  Available statuses include, RTD.", status_code),
+    };
+    _ = Spi::run_with_args(queries::INSERT_INTO_BUILD_CALL, Some(vec![
+        (PgOid::from(pg_sys::TEXTOID), build_id.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), build_flag.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), build_status.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), status.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), accepted_transformer_confidence_level.into_datum()),
+    ]));
+}
+
 #[pg_extern]
 fn source_include(  schema_pattern_include: &str, 
                     table_pattern_include: default!(Option<&str>, "NULL"), 
@@ -48,13 +222,12 @@ fn source_include(  schema_pattern_include: &str,
     let schema_pattern_exclude: &str = "a^";
     let table_pattern_exclude: &str = "a^";
     let column_pattern_exclude: &str = "a^";
-    _ = Spi::run(queries::source_object_dw( schema_pattern_include, 
-                                            table_pattern_include, 
-                                            column_pattern_include, 
-                                            schema_pattern_exclude, 
-                                            table_pattern_exclude, 
-                                            column_pattern_exclude)
-                                            .as_str());
+    source_object_dw(   schema_pattern_include, 
+                        table_pattern_include, 
+                        column_pattern_include, 
+                        schema_pattern_exclude, 
+                        table_pattern_exclude, 
+                        column_pattern_exclude);
     "Pattern Included"
 }
 
@@ -68,16 +241,154 @@ fn source_exclude(   schema_pattern_exclude: &str,
     let schema_pattern_exclude: &str = schema_pattern_exclude;
     let table_pattern_exclude: &str = table_pattern_exclude.unwrap_or(".*");
     let column_pattern_exclude: &str = column_pattern_exclude.unwrap_or(".*");
-    _ = Spi::run(queries::source_object_dw( schema_pattern_include, 
-                                            table_pattern_include, 
-                                            column_pattern_include, 
-                                            schema_pattern_exclude, 
-                                            table_pattern_exclude, 
-                                            column_pattern_exclude)
-                                            .as_str());
+    source_object_dw(   schema_pattern_include, 
+                        table_pattern_include, 
+                        column_pattern_include, 
+                        schema_pattern_exclude, 
+                        table_pattern_exclude, 
+                        column_pattern_exclude);
     "Pattern Excluded"
 }
 
+// Patterns are bound as arguments, so they may hold any character.
+fn source_object_dw(schema_pattern_include: &str, table_pattern_include: &str, column_pattern_include: &str, schema_pattern_exclude: &str, table_pattern_exclude: &str, column_pattern_exclude: &str) {
+    _ = Spi::run_with_args(queries::SOURCE_OBJECT_DW, Some(vec![
+        (PgOid::from(pg_sys::TEXTOID), schema_pattern_include.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), table_pattern_include.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), column_pattern_include.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), schema_pattern_exclude.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), table_pattern_exclude.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), column_pattern_exclude.into_datum()),
+    ]));
+}
+
+#[pg_extern]
+fn health() -> TableIterator<
+    'static,
+    (
+        name!(dw_schema, String),
+        name!(table, String),
+        name!(table_type, String),
+        name!(status, String),
+        name!(last_load_ts, Option<String>),
+        name!(rows_last_load, i64),
+        name!(matches_schema, bool),
+        name!(pending_transformer_columns, i64),
+        name!(recent_errors, i64),
+        name!(status_response, String)
+    )
+> {
+    let dw_schema = guc::get_guc(guc::PgAutoDWGuc::DwSchema).expect("DW SCHEMA GUC is not set.");
+    let dv_schema = match controller::dv_loader::dv_load_latest_schema(&dw_schema) {
+        Some((_build_id, dv_schema)) => dv_schema,
+        None => {
+            info!("No data warehouse build found for DW schema \"{}\".", dw_schema);
+            return TableIterator::new(Vec::new());
+        }
+    };
+    let rows: Vec<_> = controller::dv_health::dv_health(&dv_schema)
+        .into_iter()
+        .map(|table_health| (
+            dw_schema.clone(),
+            table_health.table_name,
+            table_health.table_type,
+            table_health.status,
+            table_health.last_load_ts,
+            table_health.rows_last_load,
+            table_health.matches_schema,
+            table_health.pending_transformer_columns,
+            table_health.recent_errors,
+            table_health.status_response,
+        ))
+        .collect();
+    TableIterator::new(rows)
+}
+
+#[pg_extern]
+fn update_context(object: &str, context: pgrx::JsonB) -> &'static str {
+    // Objects are parsed as SQL names, so unquoted identifiers fold to lower case and quoted ones are kept exactly.
+    let object_parts: Vec<String> = Spi::get_one_with_args::<Vec<String>>("SELECT parse_ident($1)", vec![
+        (PgOid::from(pg_sys::TEXTOID), object.into_datum()),
+    ])
+    .ok()
+    .flatten()
+    .unwrap_or_else(|| error!("Object \"{}\" is not supported.  Objects include, schema, schema.table, and schema.table.column.", object));
+    let (schema_name, table_name, column_name) = match object_parts.as_slice() {
+        [schema_name] => (schema_name.clone(), None, None),
+        [schema_name, table_name] => (schema_name.clone(), Some(table_name.clone()), None),
+        [schema_name, table_name, column_name] => (schema_name.clone(), Some(table_name.clone()), Some(column_name.clone())),
+        _ => error!("Object \"{}\" is not supported.  Objects include, schema, schema.table, and schema.table.column.", object),
+    };
+
+    let context_map = context.0.as_object()
+        .unwrap_or_else(|| error!("Context must be a JSON object, received {}.", context.0));
+
+    for (key, value) in context_map {
+        let is_column = column_name.is_some();
+        let is_valid = match key.as_str() {
+            "hint" => value.is_string() || value.is_null(),
+            "cron" => !is_column && (value.is_null() || value.as_str().map_or(false, |cron| {
+                utility::cron::CronSchedule::parse(cron).unwrap_or_else(|e| error!("{}", e));
+                true
+            })),
+            "sensitive" => is_column && (value.is_boolean() || value.is_null()),
+            "business_key" => is_column && (value.is_string() || value == &serde_json::Value::Bool(false) || value.is_null()),
+            "alias" => is_column && (value.is_string() || value.is_null()),
+            _ => error!("Context key \"{}\" is not supported.  Available keys include, sensitive, business_key, alias, cron, and hint.", key),
+        };
+        if !is_valid {
+            error!("Context key \"{}\" does not accept {} for object \"{}\".  Keys sensitive, business_key, and alias are set on columns, with true or false, a business key name or false, and a DW column name respectively.  Key cron is set on schemas and tables with a cron expression.  Key hint accepts text.", key, value, object);
+        }
+    }
+
+    _ = Spi::run_with_args(queries::UPSERT_CONTEXT, Some(vec![
+        (PgOid::from(pg_sys::TEXTOID), schema_name.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), table_name.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), column_name.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), context.0.to_string().into_datum()),
+    ]));
+    "Context Updated"
+}
+
+#[pg_extern]
+fn set_column_category( schema: &str,
+                        table: &str,
+                        column: &str,
+                        category: &str,
+                        business_key_name: default!(Option<&str>, "NULL")) -> &'static str {
+    let category = match category.to_lowercase().as_str() {
+        "business key part" => "Business Key Part",
+        "descriptor" => "Descriptor",
+        "descriptor - sensitive" => "Descriptor - Sensitive",
+        _ => error!("Category \"{}\" is not supported.  Available categories include, Business Key Part, Descriptor, and Descriptor - Sensitive.", category),
+    };
+
+    let business_key_name = match (category, business_key_name) {
+        ("Business Key Part", Some(business_key_name)) if !business_key_name.trim().is_empty() => business_key_name.trim().replace(" ", "_"),
+        ("Business Key Part", _) => error!("Category \"Business Key Part\" requires a business key name."),
+        (_, Some(_)) => error!("A business key name is only set on category \"Business Key Part\"."),
+        (_, None) => "NA".to_string(),
+    };
+
+    let reason = format!("Manually set with auto_dw.set_column_category to {}.", category);
+
+    // Names are matched exactly, as they appear in the catalog.
+    let manual_override_count = Spi::get_one_with_args::<i64>(queries::INSERT_MANUAL_OVERRIDE, vec![
+        (PgOid::from(pg_sys::TEXTOID), schema.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), table.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), column.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), category.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), business_key_name.into_datum()),
+        (PgOid::from(pg_sys::TEXTOID), reason.into_datum()),
+    ]);
+
+    match manual_override_count {
+        Ok(Some(count)) if count > 0 => "Column Category Set",
+        Ok(_) => error!("Column \"{}.{}.{}\" is not included for DW automation.  Include it with auto_dw.source_include.", schema, table, column),
+        Err(e) => error!("Unable to set column category: {}", e),
+    }
+}
+
 #[pg_extern]
 fn source_table() -> Result<
     TableIterator<
@@ -87,14 +398,26 @@ fn source_table() -> Result<
             name!(table, Result<Option<String>, pgrx::spi::Error>),
             name!(status, Result<Option<String>, pgrx::spi::Error>),
             name!(status_code, Result<Option<String>, pgrx::spi::Error>),
-            name!(status_response, Result<Option<String>, pgrx::spi::Error>)
+            name!(status_response, Result<Option<String>, pgrx::spi::Error>),
+            name!(column_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(queued_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(ready_to_deploy_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(awaiting_bk_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(awaiting_link_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(requires_attention_count, Result<Option<i64>, pgrx::spi::Error>),
+            name!(deployed_count, Result<Option<i64>, pgrx::spi::Error>)
         )
     >,
     spi::Error,
 > {
-    let query: &str = queries::SOURCE_TABLE_SAMPLE;
+    let accepted_transformer_confidence_level: String = 
+        utility::guc::get_guc(guc::PgAutoDWGuc::AcceptedTransformerConfidenceLevel)
+            .unwrap_or_else(|| {
+                error!("GUC: Unable to obtain parameter \"pg_auto_dw.accepted_transformer_confidence_level.\"");
+            });
+
+    let query: &str = &queries::source_table(&accepted_transformer_confidence_level);
 
-    info!("Evaluation of TABLE customer");
     Spi::connect(|client| {
         Ok(client
             .select(query, None, None)?
@@ -103,7 +426,14 @@ fn source_table() -> Result<
                 row["table"].value(), 
                 row["status"].value(),
                 row["status_code"].value(),
-                row["status_response"].value())
+                row["status_response"].value(),
+                row["column_count"].value(),
+                row["queued_count"].value(),
+                row["ready_to_deploy_count"].value(),
+                row["awaiting_bk_count"].value(),
+                row["awaiting_link_count"].value(),
+                row["requires_attention_count"].value(),
+                row["deployed_count"].value())
             )
             .collect::<Vec<_>>())
     })
@@ -120,7 +450,8 @@ fn source_column() -> Result<
             name!(column, Result<Option<String>, pgrx::spi::Error>),
             name!(status, Result<Option<String>, pgrx::spi::Error>),
             name!(confidence_level, Result<Option<String>, pgrx::spi::Error>),
-            name!(status_response, Result<Option<String>, pgrx::spi::Error>)
+            name!(status_response, Result<Option<String>, pgrx::spi::Error>),
+            name!(deployment_status, Result<Option<String>, pgrx::spi::Error>)
         )
     >,
     spi::Error,
@@ -143,7 +474,8 @@ fn source_column() -> Result<
                 row["column"].value(), 
                 row["status"].value(),
                 row["confidence_level"].value(),
-                row["status_response"].value())
+                row["status_response"].value(),
+                row["deployment_status"].value())
             )
             .collect::<Vec<_>>())
     })
@@ -161,11 +493,1107 @@ fn hash(input: &str) -> String {
 mod tests {
     use pgrx::prelude::*;
 
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::controller::dv_builder::build_sql_for_business_key;
+    use crate::controller::dv_loader::dv_data_loader_dml;
+    use crate::model::dv_schema::*;
+
     // TODO: Unit Testing
     #[pg_test]
     fn go_default() {
     }
 
+    #[pg_test]
+    fn go_dry_run_leaves_no_build() {
+        customer_source();
+
+        let statuses: Vec<String> = crate::go_flag("DryRun", "RTD").map(|(_, _, _, status, _)| status).collect();
+
+        assert!(!statuses.is_empty());
+        assert!(statuses.iter().all(|status| status == "Succeeded"));
+        assert_eq!(Some(0), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.build_call").unwrap());
+        assert_eq!(Some(0), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo").unwrap());
+        assert_eq!(Some(0), Spi::get_one::<i64>("SELECT COUNT(*) FROM pg_tables WHERE schemaname = 'dw'").unwrap());
+    }
+
+    #[pg_test]
+    fn go_push_table_loads_new_rows() {
+        customer_source();
+        crate::go_default();
+
+        Spi::run("INSERT INTO sales.customer VALUES (2, 'bob')").unwrap();
+        let statuses: Vec<String> = crate::go_flag("Push-Table", "sales.customer").map(|(_, _, _, status, _)| status).collect();
+
+        assert_eq!(vec!["Succeeded".to_string()], statuses);
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.hub_customer WHERE customer_id_bk IN ('1', '2')").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer WHERE customer_name = 'bob'").unwrap());
+    }
+
+    #[pg_test]
+    fn go_push_table_parses_quoted_table_names() {
+        Spi::run(r#"
+            CREATE SCHEMA "My.Sales";
+            CREATE TABLE "My.Sales"."Customer" (customer_id INT, customer_name TEXT);
+            INSERT INTO "My.Sales"."Customer" VALUES (1, 'ann');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("My.Sales", &[
+            ("Customer", "customer_id", "Business Key Part", Some("customer")),
+            ("Customer", "customer_name", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        Spi::run(r#"INSERT INTO "My.Sales"."Customer" VALUES (2, 'bob')"#).unwrap();
+        let statuses: Vec<String> = crate::go_flag("Push-Table", r#""My.Sales"."Customer""#).map(|(_, _, _, status, _)| status).collect();
+
+        assert_eq!(vec!["Succeeded".to_string()], statuses);
+        assert_eq!(Some(vec!["ann".to_string(), "bob".to_string()]), Spi::get_one::<Vec<String>>(r#"SELECT array_agg(customer_name ORDER BY customer_name) FROM dw."sat_Customer""#).unwrap());
+    }
+
+    #[pg_test]
+    fn go_adds_new_source_columns_to_existing_satellites() {
+        customer_source();
+        crate::go_default();
+
+        Spi::run(r#"
+            ALTER TABLE sales.customer ADD COLUMN customer_city TEXT;
+            INSERT INTO sales.customer VALUES (2, 'bob', 'Boston');
+        "#).unwrap();
+        set_column_categories("sales", &[("customer", "customer_city", "Descriptor", None)]);
+        crate::go_default();
+
+        // The satellite is altered in place, so rows loaded by the first build are kept.
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo").unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.hub_customer WHERE record_source <> 'SYSTEM'").unwrap());
+        assert_eq!(Some(true), Spi::get_one::<bool>("SELECT EXISTS (SELECT 1 FROM dw.sat_customer WHERE customer_name = 'ann' AND customer_city IS NULL)").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer WHERE customer_name = 'bob' AND customer_city = 'Boston'").unwrap());
+    }
+
+    #[pg_test(error = "Business key customer changed its parts from (customer_id) to (customer_id, customer_region), which would rehash the rows of hub_customer.  Roll back the builds of hub_customer, or give the new parts another business key name.")]
+    fn go_rejects_new_business_key_parts_of_existing_hubs() {
+        customer_source();
+        crate::go_default();
+
+        Spi::run("ALTER TABLE sales.customer ADD COLUMN customer_region TEXT").unwrap();
+        set_column_categories("sales", &[("customer", "customer_region", "Business Key Part", Some("customer"))]);
+        crate::go_default();
+    }
+
+    #[pg_test]
+    fn go_reloads_of_unchanged_data_add_no_satellite_rows() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT, customer_city TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann', NULL), (2, NULL, 'bob'), (3, 'cy', 'Boston'), (3, 'cy', 'Denver');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("customer", "customer_city", "Descriptor", None),
+        ]);
+        crate::go_default();
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.hub_customer WHERE record_source <> 'SYSTEM'").unwrap());
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer").unwrap());
+
+        // Customer 3 has two source rows, and the same one is picked by every load.
+        let _ = crate::go_flag("Push-Table", "sales.customer");
+        let _ = crate::go_flag("Push-Table", "sales.customer");
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer").unwrap());
+
+        Spi::run("UPDATE sales.customer SET customer_city = 'Austin' WHERE customer_id = 1").unwrap();
+        let _ = crate::go_flag("Push-Table", "sales.customer");
+        assert_eq!(Some(4), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer WHERE customer_city = 'Austin'").unwrap());
+    }
+
+    #[pg_test]
+    fn go_status_satellite_records_deleted_and_reappeared_keys() {
+        customer_source();
+        Spi::run(r#"
+            SET pg_auto_dw.status_tracking = true;
+            INSERT INTO sales.customer VALUES (2, 'bob');
+        "#).unwrap();
+        crate::go_default();
+        assert_eq!(Some(0), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer_status").unwrap());
+
+        let customer_2_statuses = r#"
+            SELECT array_agg(status_sat.status::TEXT ORDER BY status_sat.status)
+            FROM dw.sat_customer_status AS status_sat
+            JOIN dw.hub_customer AS hub ON status_sat.hub_customer_hk = hub.hub_customer_hk
+            WHERE hub.customer_id_bk = '2'
+        "#;
+
+        Spi::run("DELETE FROM sales.customer WHERE customer_id = 2").unwrap();
+        let _ = crate::go_flag("Push-Table", "sales.customer");
+        assert_eq!(Some(vec!["deleted".to_string()]), Spi::get_one::<Vec<String>>(customer_2_statuses).unwrap());
+
+        Spi::run("INSERT INTO sales.customer VALUES (2, 'bob')").unwrap();
+        let _ = crate::go_flag("Push-Table", "sales.customer");
+        assert_eq!(Some(vec!["deleted".to_string(), "reappeared".to_string()]), Spi::get_one::<Vec<String>>(customer_2_statuses).unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer_status").unwrap());
+    }
+
+    #[pg_test]
+    fn go_status_satellite_records_the_source_each_key_was_observed_in() {
+        customer_source();
+        Spi::run(r#"
+            CREATE SCHEMA crm;
+            CREATE TABLE crm.customer (customer_id INT, customer_email TEXT);
+            INSERT INTO sales.customer VALUES (2, 'bob');
+            SET pg_auto_dw.status_tracking = true;
+        "#).unwrap();
+        set_column_categories("crm", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_email", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        let customer_2_statuses = r#"
+            SELECT array_agg(status_sat.status::TEXT || ' ' || status_sat.record_source ORDER BY status_sat.status)
+            FROM dw.sat_customer_status AS status_sat
+            JOIN dw.hub_customer AS hub ON status_sat.hub_customer_hk = hub.hub_customer_hk
+            WHERE hub.customer_id_bk = '2'
+        "#;
+
+        // Customer 2 was loaded from sales, and reappears in crm.
+        Spi::run("DELETE FROM sales.customer WHERE customer_id = 2").unwrap();
+        crate::go_default();
+        assert_eq!(Some(vec!["deleted sales".to_string()]), Spi::get_one::<Vec<String>>(customer_2_statuses).unwrap());
+
+        Spi::run("INSERT INTO crm.customer VALUES (2, 'bob@example.com')").unwrap();
+        crate::go_default();
+        assert_eq!(Some(vec!["deleted sales".to_string(), "reappeared crm".to_string()]), Spi::get_one::<Vec<String>>(customer_2_statuses).unwrap());
+    }
+
+    #[pg_test]
+    fn go_links_hubs_through_foreign_keys() {
+        customer_orders_source();
+        crate::go_default();
+
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.link_order_customer").unwrap());
+        assert_eq!(Some(vec!["10".to_string(), "11".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(hub_order.order_id_bk::TEXT ORDER BY hub_order.order_id_bk)
+            FROM dw.link_order_customer AS link
+            JOIN dw.hub_order AS hub_order ON link.hub_order_hk = hub_order.hub_order_hk
+            JOIN dw.hub_customer AS hub_customer ON link.hub_customer_hk = hub_customer.hub_customer_hk
+            WHERE hub_customer.customer_id_bk = '1'
+        "#).unwrap());
+    }
+
+    #[pg_test]
+    fn go_refreshes_bridges_with_hub_satellite_load_timestamps() {
+        customer_orders_source();
+        crate::go_default();
+
+        // Each link row gets a snapshot holding the latest load of both hubs' satellites, as the hub PITs do.
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.bridge_order_customer").unwrap());
+        assert_eq!(Some(0), Spi::get_one::<i64>(r#"
+            SELECT COUNT(*)
+            FROM dw.bridge_order_customer AS bridge
+            LEFT JOIN dw.pit_customer AS pit_customer ON
+                bridge.hub_customer_hk = pit_customer.hub_customer_hk AND
+                bridge.snapshot_ts = pit_customer.snapshot_ts
+            LEFT JOIN dw.pit_order AS pit_order ON
+                bridge.hub_order_hk = pit_order.hub_order_hk AND
+                bridge.snapshot_ts = pit_order.snapshot_ts
+            WHERE bridge.sat_customer_load_ts IS DISTINCT FROM pit_customer.sat_customer_load_ts OR
+                bridge.sat_orders_load_ts IS DISTINCT FROM pit_order.sat_orders_load_ts OR
+                bridge.sat_customer_load_ts IS NULL OR
+                bridge.sat_orders_load_ts IS NULL
+        "#).unwrap());
+
+        // The next snapshot is not due until pg_auto_dw.pit_snapshot_interval has passed.
+        crate::refresh_pit_bridge();
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.bridge_order_customer").unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.pit_customer").unwrap());
+    }
+
+    #[pg_test]
+    fn go_links_business_keys_of_one_table() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.order_line (order_id INT, product_id INT, quantity INT);
+            INSERT INTO sales.order_line VALUES (10, 100, 2), (10, 101, 1), (11, 100, 4);
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("order_line", "order_id", "Business Key Part", Some("order")),
+            ("order_line", "product_id", "Business Key Part", Some("product")),
+            ("order_line", "quantity", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        // The table's descriptors describe the link, not either hub.
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.hub_order WHERE record_source <> 'SYSTEM'").unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.hub_product WHERE record_source <> 'SYSTEM'").unwrap());
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.link_order_product").unwrap());
+        assert_eq!(Some(4), Spi::get_one::<i32>(r#"
+            SELECT sat.quantity
+            FROM dw.sat_order_line AS sat
+            JOIN dw.link_order_product AS link ON sat.link_order_product_hk = link.link_order_product_hk
+            JOIN dw.hub_order AS hub_order ON link.hub_order_hk = hub_order.hub_order_hk
+            WHERE hub_order.order_id_bk = '11'
+        "#).unwrap());
+    }
+
+    #[pg_test]
+    fn go_merges_business_keys_shared_by_tables() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+            CREATE TABLE sales.customer_address (customer_id INT, city TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann'), (2, 'bob');
+            INSERT INTO sales.customer_address VALUES (2, 'Boston'), (3, 'Denver');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("customer_address", "customer_id", "Business Key Part", Some("customer")),
+            ("customer_address", "city", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        // One hub, loaded from both tables, with a satellite per table.
+        assert_eq!(Some(vec!["hub_customer".to_string()]), Spi::get_one::<Vec<String>>(r#"SELECT array_agg(tablename::TEXT) FROM pg_tables WHERE schemaname = 'dw' AND tablename LIKE 'hub\_%'"#).unwrap());
+        assert_eq!(Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]), Spi::get_one::<Vec<String>>("SELECT array_agg(customer_id_bk::TEXT ORDER BY customer_id_bk) FROM dw.hub_customer WHERE record_source <> 'SYSTEM'").unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer_address").unwrap());
+        assert_eq!(Some("bob Boston".to_string()), Spi::get_one::<String>("SELECT customer_name || ' ' || city FROM dw.v_customer_current WHERE customer_id = '2'").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.v_customer_current WHERE customer_id = '3' AND customer_name IS NULL AND city = 'Denver'").unwrap());
+    }
+
+    #[pg_test]
+    fn go_builds_current_views_with_latest_satellite_rows() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT, ssn TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann', '123'), (2, 'bob', '456');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("customer", "ssn", "Descriptor - Sensitive", None),
+        ]);
+        crate::go_default();
+
+        // Loads in one transaction share a load_ts, so the first load is moved back for the next to be the latest.
+        Spi::run(r#"
+            UPDATE dw.sat_customer SET load_ts = load_ts - INTERVAL '1 day';
+            UPDATE sales.customer SET customer_name = 'anna' WHERE customer_id = 1;
+        "#).unwrap();
+        crate::go_default();
+
+        assert_eq!(Some(vec!["hub_customer_hk".to_string(), "customer_id".to_string(), "customer_name".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(column_name::TEXT ORDER BY ordinal_position)
+            FROM information_schema.columns
+            WHERE table_schema = 'dw' AND table_name = 'v_customer_current'
+        "#).unwrap());
+        assert_eq!(Some(vec!["1 anna".to_string(), "2 bob".to_string()]), Spi::get_one::<Vec<String>>("SELECT array_agg(customer_id || ' ' || customer_name ORDER BY customer_id) FROM dw.v_customer_current").unwrap());
+        assert_eq!(Some(vec!["1 123".to_string(), "2 456".to_string()]), Spi::get_one::<Vec<String>>("SELECT array_agg(customer_id || ' ' || ssn ORDER BY customer_id) FROM dw.v_customer_sensitive_current").unwrap());
+    }
+
+    #[pg_test]
+    fn go_kimball_loads_dimensions_from_every_source_table() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+            CREATE TABLE sales.customer_address (customer_id INT, city TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann'), (2, 'bob');
+            INSERT INTO sales.customer_address VALUES (2, 'Boston'), (3, 'Denver');
+            CREATE SCHEMA dw;
+            SET pg_auto_dw.dw_model = 'kimball';
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("customer_address", "customer_id", "Business Key Part", Some("customer")),
+            ("customer_address", "city", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        // Descriptors of both tables are joined on the business key.
+        assert_eq!(Some(vec!["1 ann -".to_string(), "2 bob Boston".to_string(), "3 - Denver".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(customer_id_bk || ' ' || COALESCE(customer_name, '-') || ' ' || COALESCE(city, '-') ORDER BY customer_id_bk)
+            FROM dw.dim_customer
+            WHERE is_current AND dim_customer_sk <> -1
+        "#).unwrap());
+
+        // A change in either table expires the current row.
+        Spi::run("UPDATE sales.customer_address SET city = 'Austin' WHERE customer_id = 2").unwrap();
+        crate::go_default();
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.dim_customer WHERE customer_id_bk = '2'").unwrap());
+        assert_eq!(Some("bob Austin".to_string()), Spi::get_one::<String>("SELECT customer_name || ' ' || city FROM dw.dim_customer WHERE customer_id_bk = '2' AND is_current").unwrap());
+        assert_eq!(Some(4), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.dim_customer WHERE dim_customer_sk <> -1").unwrap());
+    }
+
+    #[pg_test]
+    fn go_kimball_loads_repeated_transactions_as_separate_facts() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT PRIMARY KEY, customer_name TEXT);
+            CREATE TABLE sales.purchase (store_id INT, customer_id INT REFERENCES sales.customer, amount INT);
+            INSERT INTO sales.customer VALUES (1, 'ann'), (2, 'bob');
+            INSERT INTO sales.purchase VALUES (1, 1, 5), (1, 1, 5), (1, 2, 7);
+            CREATE SCHEMA dw;
+            SET pg_auto_dw.dw_model = 'kimball';
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("purchase", "store_id", "Business Key Part", Some("store")),
+            ("purchase", "customer_id", "Descriptor", None),
+            ("purchase", "amount", "Descriptor", None),
+        ]);
+        let purchases = || Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(dim_customer.customer_name || ' ' || fact.amount ORDER BY dim_customer.customer_name, fact.amount)
+            FROM dw.fact_store_customer AS fact
+            JOIN dw.dim_customer AS dim_customer ON fact.dim_customer_sk = dim_customer.dim_customer_sk
+        "#).unwrap();
+
+        crate::go_default();
+        assert_eq!(Some(vec!["ann 5".to_string(), "ann 5".to_string(), "bob 7".to_string()]), purchases());
+
+        // Reloads add only the repetitions not loaded before.
+        crate::go_default();
+        assert_eq!(Some(vec!["ann 5".to_string(), "ann 5".to_string(), "bob 7".to_string()]), purchases());
+        Spi::run("INSERT INTO sales.purchase VALUES (1, 1, 5)").unwrap();
+        crate::go_default();
+        assert_eq!(Some(vec!["ann 5".to_string(), "ann 5".to_string(), "ann 5".to_string(), "bob 7".to_string()]), purchases());
+    }
+
+    #[pg_test]
+    fn go_kimball_loads_facts_referencing_current_dimensions() {
+        customer_orders_source();
+        Spi::run("SET pg_auto_dw.dw_model = 'kimball'").unwrap();
+        crate::go_default();
+
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.fact_order_customer").unwrap());
+        assert_eq!(Some(vec!["10 ann".to_string(), "11 ann".to_string(), "12 bob".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(dim_order.order_id_bk || ' ' || dim_customer.customer_name ORDER BY dim_order.order_id_bk)
+            FROM dw.fact_order_customer AS fact
+            JOIN dw.dim_order AS dim_order ON fact.dim_order_sk = dim_order.dim_order_sk
+            JOIN dw.dim_customer AS dim_customer ON fact.dim_customer_sk = dim_customer.dim_customer_sk
+        "#).unwrap());
+
+        // Reloading unchanged data adds no facts.
+        crate::go_default();
+        assert_eq!(Some(3), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.fact_order_customer").unwrap());
+    }
+
+    #[pg_test(error = "DW schema \"dw\" was built with another DW model or naming.  Roll back its builds, or build into another DW schema.")]
+    fn go_rejects_model_switch() {
+        customer_source();
+        crate::go_default();
+
+        Spi::run("SET pg_auto_dw.dw_model = 'kimball'").unwrap();
+        crate::go_default();
+    }
+
+    #[pg_test]
+    fn go_switches_model_after_rollback() {
+        customer_source();
+        crate::go_default();
+        let build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+        let _ = crate::go_flag("Rollback", &build_id);
+
+        Spi::run("SET pg_auto_dw.dw_model = 'kimball'").unwrap();
+        crate::go_default();
+
+        assert_eq!(Some(vec!["dim_customer".to_string()]), Spi::get_one::<Vec<String>>("SELECT array_agg(tablename::TEXT ORDER BY tablename) FROM pg_tables WHERE schemaname = 'dw'").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.dim_customer WHERE customer_name = 'ann'").unwrap());
+    }
+
+    #[pg_test]
+    fn go_rollback_drops_only_what_each_build_added() {
+        customer_source();
+        crate::go_default();
+        let first_build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        // Builds in one transaction share an insert_time, so the first build is moved back for the next to be the latest.
+        Spi::run(r#"
+            UPDATE auto_dw.dv_repo SET insert_time = insert_time - INTERVAL '1 hour';
+            ALTER TABLE sales.customer ADD COLUMN customer_city TEXT;
+        "#).unwrap();
+        set_column_categories("sales", &[("customer", "customer_city", "Descriptor", None)]);
+        crate::go_default();
+        let second_build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo ORDER BY insert_time DESC LIMIT 1").unwrap().unwrap();
+        assert_ne!(first_build_id, second_build_id);
+
+        let dw_columns = || Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(table_name || '.' || column_name ORDER BY table_name, ordinal_position)
+            FROM information_schema.columns
+            WHERE table_schema = 'dw' AND table_name IN ('hub_customer', 'sat_customer')
+        "#).unwrap();
+
+        // The second build only added a column, which is dropped with the rows the first build loaded kept.
+        let _ = crate::go_flag("Rollback", &second_build_id);
+        assert_eq!(Some(vec![
+            "hub_customer.hub_customer_hk".to_string(),
+            "hub_customer.load_ts".to_string(),
+            "hub_customer.record_source".to_string(),
+            "hub_customer.customer_id_bk".to_string(),
+            "sat_customer.hub_customer_hk".to_string(),
+            "sat_customer.load_ts".to_string(),
+            "sat_customer.record_source".to_string(),
+            "sat_customer.sat_customer_hd".to_string(),
+            "sat_customer.customer_name".to_string(),
+        ]), dw_columns());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM dw.sat_customer WHERE customer_name = 'ann'").unwrap());
+        assert_eq!(Some("Rolled Back".to_string()), Spi::get_one_with_args::<String>("SELECT DISTINCT build_status FROM auto_dw.build_call WHERE build_id = $1", vec![
+            (PgOid::from(pg_sys::TEXTOID), second_build_id.clone().into_datum()),
+        ]).unwrap());
+
+        let _ = crate::go_flag("Rollback", &first_build_id);
+        assert_eq!(Some(0), Spi::get_one::<i64>("SELECT COUNT(*) FROM pg_class JOIN pg_namespace ON pg_class.relnamespace = pg_namespace.oid WHERE nspname = 'dw'").unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo WHERE rolled_back_at IS NOT NULL").unwrap());
+    }
+
+    #[pg_test]
+    fn go_names_tables_and_columns_by_naming_template() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, "CustomerName" TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann');
+            CREATE SCHEMA dw;
+            SET pg_auto_dw.naming_template = '{"hub": "h_{name}", "satellite": "s_{name}", "hash_key": "hk_{name}", "snake_case_aliases": true}';
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "CustomerName", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        assert_eq!(Some(vec!["h_customer".to_string(), "pit_customer".to_string(), "s_customer".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(tablename::TEXT ORDER BY tablename)
+            FROM pg_tables
+            WHERE schemaname = 'dw' AND tablename NOT LIKE '%status%'
+        "#).unwrap());
+        assert_eq!(Some("1 ann".to_string()), Spi::get_one::<String>(r#"
+            SELECT hub.customer_id_bk || ' ' || sat.customer_name
+            FROM dw.h_customer AS hub
+            JOIN dw.s_customer AS sat ON hub.hk_customer = sat.hk_customer
+        "#).unwrap());
+    }
+
+    #[pg_test(error = "Naming template key \"view\" is not supported.  Available keys include, hub, link, satellite, sensitive, pit, bridge, current_view, dimension, fact, hash_key, hash_diff, business_key, surrogate_key, and snake_case_aliases.")]
+    fn go_rejects_unsupported_naming_template_keys() {
+        customer_source();
+        Spi::run(r#"SET pg_auto_dw.naming_template = '{"view": "v_{name}"}'"#).unwrap();
+        crate::go_default();
+    }
+
+    #[pg_test]
+    fn go_quotes_reserved_and_mixed_case_identifiers() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            INSERT INTO "Sales"."Order" VALUES (1, 'ann', '02134', 3), (2, 'bob', '94105', 5);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+
+        let dv_schema = quoting_dv_schema(Naming::default());
+        build_and_load(&dv_schema);
+
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."hub_Order" WHERE record_source <> 'SYSTEM'"#).unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(DISTINCT "OrderID_bk") FROM "DW Vault"."hub_Order" WHERE record_source <> 'SYSTEM'"#).unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."sat_Order Details" WHERE "user" IS NOT NULL AND "Zip Code" IS NOT NULL"#).unwrap());
+        assert_eq!(Some(8), Spi::get_one::<i64>(r#"SELECT SUM("select")::BIGINT FROM "DW Vault"."sat_Order Details""#).unwrap());
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."v_Order_current""#).unwrap());
+
+        // A second load of unchanged source rows adds nothing.
+        Spi::run(&dv_data_loader_dml(&dv_schema)).unwrap();
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."sat_Order Details""#).unwrap());
+    }
+
+    #[pg_test]
+    fn go_builds_from_mixed_case_and_reserved_source_names() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            INSERT INTO "Sales"."Order" VALUES (1, 'ann', '02134', 3), (2, 'bob', '94105', 5);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+        set_column_categories("Sales", &[
+            ("Order", "OrderID", "Business Key Part", Some("Order")),
+            ("Order", "user", "Descriptor", None),
+            ("Order", "Zip Code", "Descriptor", None),
+            ("Order", "select", "Descriptor", None),
+        ]);
+        Spi::run(r#"SET pg_auto_dw.dw_schema = 'DW Vault'"#).unwrap();
+        crate::go_default();
+
+        assert_eq!(Some(vec!["1".to_string(), "2".to_string()]), Spi::get_one::<Vec<String>>(r#"SELECT array_agg("OrderID_bk" ORDER BY "OrderID_bk") FROM "DW Vault"."hub_Order" WHERE record_source = 'Sales'"#).unwrap());
+        assert_eq!(Some("ann 02134 3".to_string()), Spi::get_one::<String>(r#"SELECT "user" || ' ' || "Zip Code" || ' ' || "select" FROM "DW Vault"."v_Order_current" WHERE "OrderID" = '1'"#).unwrap());
+
+        // A second build of unchanged source rows adds nothing.
+        crate::go_default();
+        assert_eq!(Some(2), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."sat_Order""#).unwrap());
+    }
+
+    #[pg_test]
+    fn go_builds_from_names_holding_quotes() {
+        Spi::run(r#"
+            CREATE SCHEMA "O'Brien";
+            CREATE TABLE "O'Brien".customer (customer_id INT, "Customer's Name" TEXT);
+            INSERT INTO "O'Brien".customer VALUES (1, 'ann');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("O'Brien", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "Customer's Name", "Descriptor", None),
+        ]);
+        crate::go_default();
+
+        assert_eq!(Some("O'Brien ann".to_string()), Spi::get_one::<String>(r#"SELECT record_source || ' ' || "Customer's Name" FROM dw.sat_customer"#).unwrap());
+        assert!(crate::source_column().unwrap().all(|(_, _, _, _, _, _, deployment_status)| deployment_status.unwrap().as_deref() == Some("Deployed")));
+    }
+
+    #[pg_test]
+    fn export_sql_hashes_index_names_past_63_bytes() {
+        let business_key_name = "customer_account_holder_identifier_named_past_idx_limits";
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann');
+            CREATE SCHEMA dw;
+            SET pg_auto_dw.status_tracking = false;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some(business_key_name)),
+            ("customer", "customer_name", "Descriptor", None),
+        ]);
+        crate::go_default();
+        let build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        // hub_<name>_idx and pit_<name>_idx would both be 64 bytes, which Postgres truncates.
+        let index_names = Spi::get_one::<Vec<String>>("SELECT array_agg(indexname::TEXT ORDER BY indexname) FROM pg_indexes WHERE schemaname = 'dw'").unwrap().unwrap();
+        assert_eq!(3, index_names.len());
+        assert!(index_names.iter().all(|index_name| index_name.len() <= 63 && index_name.ends_with("_idx")));
+        assert!(index_names.contains(&"sat_customer_idx".to_string()));
+        assert!(index_names.iter().any(|index_name| index_name.starts_with(&format!("hub_{}", &business_key_name[..40]))));
+        assert!(index_names.iter().any(|index_name| index_name.starts_with(&format!("pit_{}", &business_key_name[..40]))));
+
+        // The export creates the same indexes, after the tables and before the initial load.
+        let export_sql = crate::export_sql(&build_id);
+        let position = |text: &str| export_sql.find(text).unwrap_or_else(|| panic!("{} not found in the export", text));
+        for index_name in &index_names {
+            assert!(position(&format!("CREATE INDEX IF NOT EXISTS {} ON", index_name)) > position("CREATE TABLE dw.sat_customer"));
+            assert!(position(&format!("CREATE INDEX IF NOT EXISTS {} ON", index_name)) < position("-- Initial Load"));
+        }
+    }
+
+    #[pg_test]
+    fn export_dbt_hashes_each_model_from_its_own_source_table() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+            CREATE TABLE sales.customer_address (cust_id INT, city TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann');
+            INSERT INTO sales.customer_address VALUES (2, 'Boston');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("customer_address", "cust_id", "Business Key Part", Some("customer")),
+            ("customer_address", "city", "Descriptor", None),
+        ]);
+        crate::go_default();
+        let build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        let dbt_files: Vec<(String, String)> = crate::export_dbt(&build_id).collect();
+        let model = |path: &str| dbt_files.iter().find(|(file_path, _)| file_path == path).map(|(_, content)| content.clone()).unwrap_or_else(|| panic!("{} not exported", path));
+
+        // The hub selects the business key of both source tables.
+        let hub_model = model("models/hubs/hub_customer.sql");
+        assert!(hub_model.contains(r#"FROM {{ source("sales", "customer") }} AS stg"#));
+        assert!(hub_model.contains(r#"FROM {{ source("sales", "customer_address") }} AS stg"#));
+        assert!(hub_model.contains("ARRAY[stg.cust_id::TEXT]"));
+
+        // Each satellite hashes the hub key from the columns of its own table.
+        let sat_customer_model = model("models/satellites/sat_customer.sql");
+        assert!(sat_customer_model.contains("ARRAY[stg.customer_id::TEXT]"));
+        assert!(!sat_customer_model.contains("cust_id"));
+        let sat_customer_address_model = model("models/satellites/sat_customer_address.sql");
+        assert!(sat_customer_address_model.contains(r#"FROM {{ source("sales", "customer_address") }} AS stg"#));
+        assert!(sat_customer_address_model.contains("ARRAY[stg.cust_id::TEXT]"));
+        assert!(!sat_customer_address_model.contains("customer_id::TEXT"));
+    }
+
+    #[pg_test]
+    fn diagram_draws_built_tables_and_their_sources() {
+        customer_source();
+        crate::go_default();
+        let build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        let mermaid = crate::diagram(&build_id, "Mermaid");
+        assert!(mermaid.starts_with("erDiagram\n"));
+        assert!(mermaid.contains("    hub_customer[\"hub_customer\"] {\n        VARCHAR hub_customer_hk PK\n"));
+        assert!(mermaid.contains("        VARCHAR customer_id_bk\n"));
+        assert!(mermaid.contains("    sat_customer[\"sat_customer\"] {\n        VARCHAR hub_customer_hk FK\n"));
+        assert!(mermaid.contains("    src_sales_customer[\"sales.customer\"] {\n"));
+        assert!(mermaid.contains("    src_sales_customer ||--o{ hub_customer : \"loads\"\n"));
+        assert!(mermaid.contains("    src_sales_customer ||--o{ sat_customer : \"loads\"\n"));
+        assert!(mermaid.contains("    hub_customer ||--|{ sat_customer : \"hub_customer_hk\"\n"));
+
+        let dot = crate::diagram(&build_id, "Graphviz");
+        assert!(dot.starts_with("digraph \"dw\" {\n"));
+        assert!(dot.contains("    \"source:sales.customer\" -> \"dw:hub_customer\" [label=\"loads\"];\n"));
+        assert!(dot.contains("    \"dw:hub_customer\" -> \"dw:sat_customer\" [label=\"hub_customer_hk\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[pg_test(error = "Format \"svg\" is not supported.  Available formats include, Mermaid and Graphviz.")]
+    fn diagram_rejects_unsupported_formats() {
+        customer_source();
+        crate::go_default();
+        let build_id = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        crate::diagram(&build_id, "svg");
+    }
+
+    #[pg_test]
+    fn go_derives_snake_case_names_from_source_names() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            INSERT INTO "Sales"."Order" VALUES (1, 'ann', '02134', 3);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+
+        let naming = Naming { snake_case_aliases: true, ..Naming::default() };
+        let dv_schema = quoting_dv_schema(naming);
+        build_and_load(&dv_schema);
+
+        assert_eq!(Some(1), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."sat_Order Details" WHERE zip_code = '02134' AND "user" = 'ann'"#).unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>(r#"SELECT COUNT(order_id_bk) FROM "DW Vault"."hub_Order""#).unwrap());
+    }
+
+    #[pg_test]
+    fn apply_schema_builds_edited_schema() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            INSERT INTO "Sales"."Order" VALUES (1, 'ann', '02134', 3);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+
+        // Catalog details are filled in by apply_schema, so a hand-edited schema only needs names.
+        let mut schema_json = serde_json::to_value(quoting_dv_schema(Naming::default())).unwrap();
+        schema_json["Business Keys"][0]["Descriptors"][2]["Orbit"] = serde_json::json!("Order Counts");
+        schema_json["Business Keys"][0]["Descriptors"][2]["Descriptor Link"]["Source Column Data"]["Column Type"] = serde_json::json!("");
+
+        crate::apply_schema(pgrx::JsonB(schema_json));
+
+        assert_eq!(Some(1), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM "DW Vault"."sat_Order Counts" WHERE "select" = 3"#).unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo").unwrap());
+    }
+
+    #[pg_test(error = "Schema could not be applied:\nSource column \"Sales.Order.missing\" does not exist.")]
+    fn apply_schema_rejects_missing_source_columns() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+
+        let mut schema_json = serde_json::to_value(quoting_dv_schema(Naming::default())).unwrap();
+        schema_json["Business Keys"][0]["Descriptors"][0]["Descriptor Link"]["Source Column Data"]["Column Name"] = serde_json::json!("missing");
+
+        crate::apply_schema(pgrx::JsonB(schema_json));
+    }
+
+    #[pg_test]
+    fn apply_schema_builds_edited_go_schema() {
+        customer_source();
+        crate::go_default();
+
+        // The descriptor proposed by go is renamed and moved to a satellite of its own.
+        let mut schema_json = Spi::get_one::<pgrx::JsonB>("SELECT schema::jsonb FROM auto_dw.dv_repo").unwrap().unwrap().0;
+        schema_json["Business Keys"][0]["Descriptors"][0]["Descriptor Link"]["Alias"] = serde_json::json!("full_name");
+        schema_json["Business Keys"][0]["Descriptors"][0]["Orbit"] = serde_json::json!("customer_profile");
+        crate::apply_schema(pgrx::JsonB(schema_json));
+
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo").unwrap());
+        assert_eq!(Some(vec!["1 ann".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(hub.customer_id_bk || ' ' || sat.full_name)
+            FROM dw.hub_customer AS hub
+            JOIN dw.sat_customer_profile AS sat ON sat.hub_customer_hk = hub.hub_customer_hk
+        "#).unwrap());
+    }
+
+    #[pg_test]
+    fn identical_builds_store_identical_schemas() {
+        customer_orders_source();
+
+        // IDs are derived from each object's identity, so only the dates differ.
+        crate::go_default();
+        crate::go_default();
+
+        assert_eq!(Some(2), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.dv_repo").unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>(r#"SELECT COUNT(DISTINCT schema::jsonb - 'Create Date' - 'Modified Date') FROM auto_dw.dv_repo"#).unwrap());
+        assert_eq!(Some(1), Spi::get_one::<i64>(r#"SELECT COUNT(*) FROM (SELECT DISTINCT schema::jsonb -> 'Links' AS links FROM auto_dw.dv_repo) AS schemas WHERE jsonb_array_length(links) = 1"#).unwrap());
+
+        // Schemas of the same DW schema built into another model or named otherwise get other IDs.
+        let naming = Naming::default();
+        let data_vault_id = dv_schema_id("dw", DWModel::DataVault, &naming);
+        assert_ne!(data_vault_id, dv_schema_id("dw", DWModel::Kimball, &naming));
+        assert_ne!(data_vault_id, dv_schema_id("dw", DWModel::DataVault, &Naming { hub: "h_{name}".to_string(), ..Naming::default() }));
+    }
+
+    #[pg_test]
+    fn schema_diff_lists_descriptor_changes() {
+        Spi::run(r#"
+            CREATE SCHEMA "Sales";
+            CREATE TABLE "Sales"."Order" ("OrderID" INT, "user" TEXT, "Zip Code" TEXT, "select" INT);
+            CREATE SCHEMA "DW Vault";
+        "#).unwrap();
+
+        let schema_json = serde_json::to_value(quoting_dv_schema(Naming::default())).unwrap();
+        let build_id_a = crate::controller::dv_apply::dv_apply_schema(schema_json.clone());
+
+        let mut schema_json = schema_json;
+        schema_json["Business Keys"][0]["Descriptors"][0]["Is Sensitive"] = serde_json::json!(true);
+        schema_json["Business Keys"][0]["Descriptors"].as_array_mut().unwrap().remove(2);
+        let build_id_b = crate::controller::dv_apply::dv_apply_schema(schema_json);
+
+        let changes: Vec<(String, String, Option<String>, String)> = crate::controller::dv_builds::dv_schema_diff(&build_id_a, &build_id_b)
+            .into_iter()
+            .map(|schema_change| (schema_change.change_type, schema_change.object_type, schema_change.parent, schema_change.name))
+            .collect();
+
+        assert_eq!(vec![
+            ("Changed".to_string(), "Descriptor".to_string(), Some("Order".to_string()), "user".to_string()),
+            ("Removed".to_string(), "Descriptor".to_string(), Some("Order".to_string()), "select".to_string()),
+        ], changes);
+    }
+
+    #[pg_test]
+    fn schema_diff_lists_columns_added_between_go_builds() {
+        customer_source();
+        crate::go_default();
+        let build_id_a = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo").unwrap().unwrap();
+
+        // Builds in one transaction share an insert_time, so the first build is moved back for the next to be the latest.
+        Spi::run(r#"
+            UPDATE auto_dw.dv_repo SET insert_time = insert_time - INTERVAL '1 hour';
+            ALTER TABLE sales.customer ADD COLUMN customer_city TEXT;
+        "#).unwrap();
+        set_column_categories("sales", &[("customer", "customer_city", "Descriptor", None)]);
+        crate::go_default();
+        let build_id_b = Spi::get_one::<String>("SELECT build_id FROM auto_dw.dv_repo ORDER BY insert_time DESC LIMIT 1").unwrap().unwrap();
+
+        let changes: Vec<(String, String, Option<String>, String, String)> = crate::schema_diff(&build_id_a, &build_id_b).collect();
+        assert_eq!(vec![
+            ("Added".to_string(), "Descriptor".to_string(), Some("customer".to_string()), "customer_city".to_string(), "sales.customer.customer_city text".to_string()),
+        ], changes);
+
+        // The reverse diff removes it again.
+        let changes: Vec<(String, String, Option<String>, String, String)> = crate::schema_diff(&build_id_b, &build_id_a).collect();
+        assert_eq!(vec![
+            ("Removed".to_string(), "Descriptor".to_string(), Some("customer".to_string()), "customer_city".to_string(), "sales.customer.customer_city text".to_string()),
+        ], changes);
+    }
+
+    #[pg_test]
+    fn source_column_reports_deployment_status_beside_status() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.region (region_id INT PRIMARY KEY);
+            CREATE TABLE sales.customer (customer_id INT, region_id INT REFERENCES sales.region, customer_name TEXT);
+            INSERT INTO sales.region VALUES (1);
+            INSERT INTO sales.customer VALUES (1, 1, 'ann');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "region_id", "Descriptor", None),
+            ("customer", "customer_name", "Descriptor", None),
+        ]);
+        let source_columns = || -> Vec<String> {
+            crate::source_column().unwrap()
+                .map(|(schema, table, column, status, confidence_level, _, deployment_status)| [schema, table, column, status, confidence_level, deployment_status]
+                    .into_iter()
+                    .map(|value| value.unwrap().unwrap_or_default())
+                    .collect::<Vec<String>>()
+                    .join(" | "))
+                .collect()
+        };
+
+        // sales.region is queued for the transformer, so its foreign key holds region_id back.
+        assert_eq!(vec![
+            "sales | customer | customer_id | Ready to Deploy | 100% | Not Deployed",
+            "sales | customer | region_id | Ready to Deploy | 100% | Awaiting Link",
+            "sales | customer | customer_name | Ready to Deploy | 100% | Not Deployed",
+            "sales | region | region_id | Queued for Processing | - | Not Deployed",
+        ], source_columns());
+
+        crate::go_default();
+        assert_eq!(vec![
+            "sales | customer | customer_id | Ready to Deploy | 100% | Deployed",
+            "sales | customer | region_id | Ready to Deploy | 100% | Deployed",
+            "sales | customer | customer_name | Ready to Deploy | 100% | Deployed",
+            "sales | region | region_id | Queued for Processing | - | Not Deployed",
+        ], source_columns());
+    }
+
+    #[pg_test]
+    fn set_column_category_matches_names_exactly() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE SCHEMA "Sales";
+            CREATE TABLE sales.customer (customer_id INT);
+            CREATE TABLE "Sales".customer (customer_id INT);
+        "#).unwrap();
+        crate::source_include("^(sales|Sales)$", None, None);
+
+        crate::set_column_category("Sales", "customer", "customer_id", "Business Key Part", Some("customer"));
+
+        assert_eq!(Some(vec!["Sales".to_string()]), Spi::get_one::<Vec<String>>(r#"
+            SELECT array_agg(s.schema_name::TEXT)
+            FROM auto_dw.transformer_responses AS t
+            JOIN auto_dw.source_objects AS s ON t.fk_source_objects = s.pk_source_objects
+            WHERE t.manual_override
+        "#).unwrap());
+    }
+
+    #[pg_test]
+    fn source_column_holds_manual_overrides_awaiting_business_key() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+        "#).unwrap();
+        crate::source_include("^sales$", None, None);
+        Spi::run(r#"
+            INSERT INTO auto_dw.transformer_responses (fk_source_objects, model_name, category, business_key_name, confidence_score, reason, manual_override)
+            SELECT pk_source_objects, 'test', 'Business Key Part', 'customer', 0.5, 'Unsure.', FALSE
+            FROM auto_dw.source_objects
+            WHERE table_name = 'customer' AND column_name = 'customer_id' AND current_flag = 'Y'
+        "#).unwrap();
+        crate::set_column_category("sales", "customer", "customer_name", "Descriptor", None);
+
+        let customer_name: Vec<(String, String)> = crate::source_column().unwrap()
+            .filter(|(_, _, column, _, _, _, _)| column.as_ref().unwrap().as_deref() == Some("customer_name"))
+            .map(|(_, _, _, status, _, status_response, _)| (status.unwrap().unwrap(), status_response.unwrap().unwrap()))
+            .collect();
+        assert_eq!(1, customer_name.len());
+        assert_eq!("Ready to Deploy - Awaiting Business Key (BK)", customer_name[0].0);
+        assert!(customer_name[0].1.starts_with("Status: Ready to Deploy - Awaiting Business Key (BK): Manual Override: "));
+    }
+
+    #[pg_test]
+    fn health_counts_errors_naming_each_table() {
+        customer_source();
+        crate::go_default();
+
+        // Table names are matched as whole identifiers, so longer names holding them, such as columns, are not.
+        Spi::run(r#"
+            INSERT INTO auto_dw.log (process, level, message) VALUES
+                ('test', 'ERROR', 'Load of "dw"."hub_customer" failed.'),
+                ('test', 'ERROR', 'Load of dw.sat_customer failed.'),
+                ('test', 'ERROR', 'Load of HUB_CUSTOMER failed.'),
+                ('test', 'ERROR', 'Load of hubXcustomer failed.'),
+                ('test', 'ERROR', 'Column hub_customer_hk of sat_customer_address is null.'),
+                ('test', 'INFO', 'Load of hub_customer succeeded.');
+        "#).unwrap();
+
+        let health: Vec<(String, String, i64)> = crate::health()
+            .map(|(_, table, table_type, _, _, _, _, _, recent_errors, _)| (table, table_type, recent_errors))
+            .filter(|(table, _, _)| table == "hub_customer" || table == "sat_customer")
+            .collect();
+        assert_eq!(vec![
+            ("hub_customer".to_string(), "Hub".to_string(), 1),
+            ("sat_customer".to_string(), "Satellite".to_string(), 1),
+        ], health);
+    }
+
+    #[pg_test]
+    fn update_context_combines_hints_and_removes_null_keys() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+        "#).unwrap();
+        crate::source_include("^sales$", None, None);
+
+        crate::update_context("Sales", pgrx::JsonB(serde_json::json!({"hint": "Retail sales."})));
+        crate::update_context("sales.customer", pgrx::JsonB(serde_json::json!({"hint": "One row per customer.", "cron": "0 2 * * *"})));
+        crate::update_context("sales.customer.customer_id", pgrx::JsonB(serde_json::json!({"hint": "Assigned at sign up.", "business_key": "customer"})));
+
+        let table_oid = Spi::get_one::<pg_sys::Oid>("SELECT 'sales.customer'::REGCLASS::OID").unwrap().unwrap();
+        let column_contexts = || Spi::get_one_with_args::<Vec<String>>(&format!(r#"
+            SELECT array_agg(concat_ws(' | ', column_ordinal_position, hint, is_business_key, business_key_name, is_sensitive) ORDER BY column_ordinal_position)
+            FROM ({}) AS column_context
+        "#, crate::model::queries::SOURCE_COLUMN_CONTEXT.trim().trim_end_matches(';')), vec![
+            (PgOid::from(pg_sys::OIDOID), table_oid.into_datum()),
+        ]).unwrap();
+
+        assert_eq!(Some(vec![
+            "1 | Retail sales. One row per customer. Assigned at sign up. | t | customer".to_string(),
+            "2 | Retail sales. One row per customer.".to_string(),
+        ]), column_contexts());
+
+        // Keys set to null are removed, leaving the others.
+        crate::update_context("sales.customer.customer_id", pgrx::JsonB(serde_json::json!({"hint": null})));
+        assert_eq!(Some(vec![
+            "1 | Retail sales. One row per customer. | t | customer".to_string(),
+            "2 | Retail sales. One row per customer.".to_string(),
+        ]), column_contexts());
+        assert_eq!(Some("0 2 * * *".to_string()), Spi::get_one::<String>("SELECT context->>'cron' FROM auto_dw.context WHERE schema_name = 'sales' AND table_name = 'customer' AND column_name IS NULL").unwrap());
+    }
+
+    #[pg_test]
+    fn update_context_matches_quoted_names_exactly() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE SCHEMA "Sales";
+            CREATE TABLE sales.customer (customer_id INT);
+            CREATE TABLE "Sales".customer (customer_id INT);
+        "#).unwrap();
+        crate::source_include("^(sales|Sales)$", None, None);
+
+        crate::update_context(r#""Sales".customer.customer_id"#, pgrx::JsonB(serde_json::json!({"business_key": "customer"})));
+
+        let business_key_name = |table: &str| {
+            let table_oid = Spi::get_one::<pg_sys::Oid>(&format!("SELECT '{}'::REGCLASS::OID", table)).unwrap().unwrap();
+            Spi::get_one_with_args::<String>(&format!("SELECT business_key_name FROM ({}) AS column_context",
+                crate::model::queries::SOURCE_COLUMN_CONTEXT.trim().trim_end_matches(';')), vec![
+                (PgOid::from(pg_sys::OIDOID), table_oid.into_datum()),
+            ]).unwrap()
+        };
+        assert_eq!(Some("customer".to_string()), business_key_name(r#""Sales".customer"#));
+        assert_eq!(None, business_key_name("sales.customer"));
+    }
+
+    #[pg_test(error = "Context key \"cron\" does not accept \"0 2 * * *\" for object \"sales.customer.customer_id\".  Keys sensitive, business_key, and alias are set on columns, with true or false, a business key name or false, and a DW column name respectively.  Key cron is set on schemas and tables with a cron expression.  Key hint accepts text.")]
+    fn update_context_rejects_keys_on_other_objects() {
+        crate::update_context("sales.customer.customer_id", pgrx::JsonB(serde_json::json!({"cron": "0 2 * * *"})));
+    }
+
+    #[pg_test]
+    fn go_names_dw_columns_by_alias_context() {
+        customer_source();
+        crate::update_context("sales.customer.customer_name", pgrx::JsonB(serde_json::json!({"alias": "full_name"})));
+        crate::go_default();
+
+        assert_eq!(Some("ann".to_string()), Spi::get_one::<String>("SELECT full_name FROM dw.sat_customer").unwrap());
+    }
+
+    #[pg_test]
+    fn source_include_binds_patterns() {
+        Spi::run(r#"
+            CREATE SCHEMA "O'Brien";
+            CREATE TABLE "O'Brien".customer (customer_id INT, "Customer's Name" TEXT);
+        "#).unwrap();
+
+        crate::source_include("^O'Brien$", None, Some("'"));
+
+        assert_eq!(Some(1), Spi::get_one::<i64>("SELECT COUNT(*) FROM auto_dw.source_objects WHERE schema_name = 'O''Brien' AND current_flag = 'Y'").unwrap());
+    }
+
+    // sales.customer, keyed by customer_id, with its columns categorized for DW automation into dw.
+    fn customer_source() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT, customer_name TEXT);
+            INSERT INTO sales.customer VALUES (1, 'ann');
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+        ]);
+    }
+
+    // sales.customer and sales.orders, whose foreign key links each order to its customer, categorized into dw.
+    fn customer_orders_source() {
+        Spi::run(r#"
+            CREATE SCHEMA sales;
+            CREATE TABLE sales.customer (customer_id INT PRIMARY KEY, customer_name TEXT);
+            CREATE TABLE sales.orders (order_id INT, customer_id INT REFERENCES sales.customer, amount INT);
+            INSERT INTO sales.customer VALUES (1, 'ann'), (2, 'bob');
+            INSERT INTO sales.orders VALUES (10, 1, 5), (11, 1, 7), (12, 2, 9);
+            CREATE SCHEMA dw;
+        "#).unwrap();
+        set_column_categories("sales", &[
+            ("customer", "customer_id", "Business Key Part", Some("customer")),
+            ("customer", "customer_name", "Descriptor", None),
+            ("orders", "order_id", "Business Key Part", Some("order")),
+            ("orders", "customer_id", "Descriptor", None),
+            ("orders", "amount", "Descriptor", None),
+        ]);
+    }
+
+    // Includes a schema for DW automation and sets the category of its columns, so go builds without the transformer.
+    fn set_column_categories(schema: &str, column_categories: &[(&str, &str, &str, Option<&str>)]) {
+        crate::source_include(&format!("^{}$", schema), None, None);
+        for (table, column, category, business_key_name) in column_categories {
+            crate::set_column_category(schema, table, column, category, *business_key_name);
+        }
+        Spi::run("SET pg_auto_dw.dw_schema = 'dw'").unwrap();
+    }
+
+    fn build_and_load(dv_schema: &DVSchema) {
+        for business_key in &dv_schema.business_keys {
+            Spi::run(&build_sql_for_business_key(dv_schema, None, business_key)).unwrap();
+        }
+        Spi::run(&dv_data_loader_dml(dv_schema)).unwrap();
+    }
+
+    // A hub over "Sales"."Order" whose descriptors are a reserved word, a name with a space, and a keyword.
+    fn quoting_dv_schema(naming: Naming) -> DVSchema {
+        let column = |column_name: &str, column_ordinal_position: i16, column_type_name: &str| ColumnData {
+            id: Uuid::new_v4(),
+            system_id: 0,
+            schema_name: "Sales".to_string(),
+            table_oid: 0,
+            table_name: "Order".to_string(),
+            column_name: column_name.to_string(),
+            column_ordinal_position,
+            column_type_name: column_type_name.to_string(),
+        };
+        let descriptor = |column_data: ColumnData| Descriptor {
+            id: Uuid::new_v4(),
+            descriptor_link: DescriptorLink {
+                id: Uuid::new_v4(),
+                alias: naming.alias(&column_data.column_name),
+                source_column: Some(column_data),
+                target_column: None,
+            },
+            orbit: "Order Details".to_string(),
+            is_sensitive: false,
+        };
+
+        let now = Utc::now().naive_utc();
+        DVSchema {
+            id: Uuid::new_v4(),
+            dw_schema: "DW Vault".to_string(),
+            create_timestamp_gmt: now,
+            modified_timestamp_gmt: now,
+            business_keys: vec![BusinessKey {
+                id: Uuid::new_v4(),
+                name: "Order".to_string(),
+                business_key_part_links: vec![BusinessKeyPartLink {
+                    id: Uuid::new_v4(),
+                    alias: naming.alias("OrderID"),
+                    source_columns: vec![column("OrderID", 1, "integer")],
+                    target_column: None,
+                }],
+                descriptors: vec![
+                    descriptor(column("user", 2, "text")),
+                    descriptor(column("Zip Code", 3, "text")),
+                    descriptor(column("select", 4, "integer")),
+                ],
+                status_tracking: false,
+            }],
+            links: Vec::new(),
+            dw_model: DWModel::DataVault,
+            naming,
+        }
+    }
+
 }
 
 /// This module is required by `cargo pgrx test` invocations.
@@ -2,23 +2,304 @@ use uuid::Uuid;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+// Dates and catalog details of source columns default when missing, so schemas edited by hand for
+// auto_dw.apply_schema only need names.  IDs are derived when a schema is deployed, see DVSchema::derive_ids.
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DVSchema {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
     #[serde(rename = "DW Schema")]
     pub dw_schema: String,
-    #[serde(rename = "Create Date")]
+    #[serde(rename = "Create Date", default)]
     pub create_timestamp_gmt: NaiveDateTime,
-    #[serde(rename = "Modified Date")]
+    #[serde(rename = "Modified Date", default)]
     pub modified_timestamp_gmt: NaiveDateTime,
     #[serde(rename = "Business Keys")]
     pub business_keys: Vec<BusinessKey>,
+    #[serde(rename = "Links", default)]
+    pub links: Vec<Link>,
+    #[serde(rename = "DW Model", default)]
+    pub dw_model: DWModel,
+    #[serde(rename = "Naming", default)]
+    pub naming: Naming,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+// The table structures a DVSchema is built into.  Schemas predating this setting are Data Vault.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum DWModel {
+    #[default]
+    #[serde(rename = "Data Vault")]
+    DataVault,
+    #[serde(rename = "Kimball")]
+    Kimball,
+}
+
+// Templates naming the tables and columns a DVSchema is built into.  {name} stands for the business key, link,
+// satellite, or alias being named and {table} for the table a key column belongs to.  Schemas predating this
+// setting use the defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Naming {
+    #[serde(rename = "Hub")]
+    pub hub: String,
+    #[serde(rename = "Link")]
+    pub link: String,
+    #[serde(rename = "Satellite")]
+    pub satellite: String,
+    #[serde(rename = "Sensitive")]
+    pub sensitive: String,
+    #[serde(rename = "PIT")]
+    pub pit: String,
+    #[serde(rename = "Bridge")]
+    pub bridge: String,
+    #[serde(rename = "Current View")]
+    pub current_view: String,
+    #[serde(rename = "Dimension")]
+    pub dimension: String,
+    #[serde(rename = "Fact")]
+    pub fact: String,
+    #[serde(rename = "Hash Key")]
+    pub hash_key: String,
+    #[serde(rename = "Hash Diff")]
+    pub hash_diff: String,
+    #[serde(rename = "Business Key")]
+    pub business_key: String,
+    #[serde(rename = "Surrogate Key")]
+    pub surrogate_key: String,
+    #[serde(rename = "Snake Case Aliases")]
+    pub snake_case_aliases: bool,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Naming {
+            hub: "hub_{name}".to_string(),
+            link: "link_{name}".to_string(),
+            satellite: "sat_{name}".to_string(),
+            sensitive: "{name}_sensitive".to_string(),
+            pit: "pit_{name}".to_string(),
+            bridge: "bridge_{name}".to_string(),
+            current_view: "v_{name}_current".to_string(),
+            dimension: "dim_{name}".to_string(),
+            fact: "fact_{name}".to_string(),
+            hash_key: "{table}_hk".to_string(),
+            hash_diff: "{table}_hd".to_string(),
+            business_key: "{name}_bk".to_string(),
+            surrogate_key: "{table}_sk".to_string(),
+            snake_case_aliases: false,
+        }
+    }
+}
+
+impl Naming {
+    pub fn hub(&self, name: &str) -> String {
+        fill(&self.hub, name, name)
+    }
+
+    pub fn hub_hash_key(&self, name: &str) -> String {
+        fill(&self.hash_key, name, &self.hub(name))
+    }
+
+    pub fn link(&self, name: &str) -> String {
+        fill(&self.link, name, name)
+    }
+
+    pub fn link_hash_key(&self, name: &str) -> String {
+        fill(&self.hash_key, name, &self.link(name))
+    }
+
+    // Satellite named by its key, the orbit of its descriptors along with whether they are sensitive.
+    pub fn satellite(&self, satellite_key: &str) -> String {
+        fill(&self.satellite, satellite_key, satellite_key)
+    }
+
+    pub fn satellite_hash_diff(&self, satellite_key: &str) -> String {
+        fill(&self.hash_diff, satellite_key, &self.satellite(satellite_key))
+    }
+
+    pub fn descriptor_satellite(&self, descriptor: &Descriptor) -> String {
+        self.satellite(&self.descriptor_satellite_key(descriptor))
+    }
+
+    pub fn descriptor_satellite_hash_diff(&self, descriptor: &Descriptor) -> String {
+        self.satellite_hash_diff(&self.descriptor_satellite_key(descriptor))
+    }
+
+    fn descriptor_satellite_key(&self, descriptor: &Descriptor) -> String {
+        if descriptor.is_sensitive {
+            self.sensitive(&descriptor.orbit)
+        } else {
+            descriptor.orbit.clone()
+        }
+    }
+
+    pub fn status_satellite(&self, name: &str) -> String {
+        self.satellite(&format!("{}_status", name))
+    }
+
+    pub fn sensitive(&self, name: &str) -> String {
+        fill(&self.sensitive, name, name)
+    }
+
+    pub fn pit(&self, name: &str) -> String {
+        fill(&self.pit, name, name)
+    }
+
+    pub fn bridge(&self, name: &str) -> String {
+        fill(&self.bridge, name, name)
+    }
+
+    pub fn current_view(&self, name: &str) -> String {
+        fill(&self.current_view, name, name)
+    }
+
+    pub fn sensitive_current_view(&self, name: &str) -> String {
+        self.current_view(&self.sensitive(name))
+    }
+
+    pub fn dimension(&self, name: &str) -> String {
+        fill(&self.dimension, name, name)
+    }
+
+    pub fn sensitive_dimension(&self, name: &str) -> String {
+        self.dimension(&self.sensitive(name))
+    }
+
+    // Business key hash shared by the dimensions of a business key.
+    pub fn dimension_hash_key(&self, name: &str) -> String {
+        fill(&self.hash_key, name, name)
+    }
+
+    pub fn fact(&self, name: &str) -> String {
+        fill(&self.fact, name, name)
+    }
+
+    pub fn hash_diff(&self, name: &str, table: &str) -> String {
+        fill(&self.hash_diff, name, table)
+    }
+
+    pub fn surrogate_key(&self, name: &str, table: &str) -> String {
+        fill(&self.surrogate_key, name, table)
+    }
+
+    pub fn business_key(&self, alias: &str) -> String {
+        fill(&self.business_key, alias, alias)
+    }
+
+    // Alias of a source column, as lowercase snake_case when configured.
+    pub fn alias(&self, column_name: &str) -> String {
+        if !self.snake_case_aliases {
+            return column_name.to_string();
+        }
+
+        let mut alias = String::new();
+        let mut previous: Option<char> = None;
+        for character in column_name.chars() {
+            if character.is_alphanumeric() {
+                let is_word_start = character.is_uppercase() && previous.map_or(false, |previous| previous.is_lowercase() || previous.is_numeric());
+                if is_word_start {
+                    alias.push('_');
+                }
+                alias.extend(character.to_lowercase());
+            } else if !alias.is_empty() && !alias.ends_with('_') {
+                alias.push('_');
+            }
+            previous = Some(character);
+        }
+        alias.trim_end_matches('_').to_string()
+    }
+}
+
+fn fill(template: &str, name: &str, table: &str) -> String {
+    template.replace("{name}", name).replace("{table}", table)
+}
+
+// IDs are UUIDv5s derived from what identifies each object, so rebuilding an identical model gives identical IDs.
+// Schemas are identified by their DW schema, model, and naming, columns by their system, table, and position,
+// business keys and links by name, and the objects within them by alias or referenced business key.
+const ID_NAMESPACE: Uuid = Uuid::from_u128(0x6f0c_2a4e_8d3b_5c91_a7e2_4b1f_9d60_c3e8);
+
+pub fn dv_schema_id(dw_schema: &str, dw_model: DWModel, naming: &Naming) -> Uuid {
+    let naming_json = serde_json::to_string(naming).unwrap_or_default();
+    Uuid::new_v5(&ID_NAMESPACE, format!("DV Schema:{}:{:?}:{}", dw_schema, dw_model, naming_json).as_bytes())
+}
+
+pub fn business_key_id(business_key_name: &str) -> Uuid {
+    Uuid::new_v5(&ID_NAMESPACE, format!("Business Key:{}", business_key_name).as_bytes())
+}
+
+pub fn business_key_part_link_id(business_key_name: &str, alias: &str) -> Uuid {
+    Uuid::new_v5(&business_key_id(business_key_name), format!("Business Key Part Link:{}", alias).as_bytes())
+}
+
+pub fn link_id(link_name: &str) -> Uuid {
+    Uuid::new_v5(&ID_NAMESPACE, format!("Link:{}", link_name).as_bytes())
+}
+
+pub fn link_hub_reference_id(link_name: &str, business_key_name: &str) -> Uuid {
+    Uuid::new_v5(&link_id(link_name), format!("Hub Reference:{}", business_key_name).as_bytes())
+}
+
+// Descriptors are created before the hub or link they belong to is known, so they are identified by their source column.
+pub fn descriptor_id(source_column: Option<&ColumnData>, alias: &str) -> Uuid {
+    let source_column_id = source_column.map(|source_column| source_column.id).unwrap_or_else(Uuid::nil);
+    Uuid::new_v5(&source_column_id, format!("Descriptor:{}", alias).as_bytes())
+}
+
+pub fn descriptor_link_id(descriptor_id: &Uuid) -> Uuid {
+    Uuid::new_v5(descriptor_id, b"Descriptor Link")
+}
+
+pub fn column_data_id(system_id: i64, table_oid: u32, column_ordinal_position: i16) -> Uuid {
+    Uuid::new_v5(&ID_NAMESPACE, format!("Column:{}.{}.{}", system_id, table_oid, column_ordinal_position).as_bytes())
+}
+
+impl DVSchema {
+    // Sets every ID from its object's identity, replacing IDs from hand-edited schemas and from builds that predate derived IDs.
+    pub fn derive_ids(&mut self) {
+        self.id = dv_schema_id(&self.dw_schema, self.dw_model, &self.naming);
+
+        for business_key in &mut self.business_keys {
+            business_key.id = business_key_id(&business_key.name);
+            for part_link in &mut business_key.business_key_part_links {
+                part_link.id = business_key_part_link_id(&business_key.name, &part_link.alias);
+                part_link.source_columns.iter_mut().for_each(ColumnData::derive_id);
+                part_link.target_column.iter_mut().for_each(ColumnData::derive_id);
+            }
+            business_key.descriptors.iter_mut().for_each(Descriptor::derive_ids);
+        }
+
+        for link in &mut self.links {
+            link.id = link_id(&link.name);
+            for hub_reference in &mut link.hub_references {
+                hub_reference.id = link_hub_reference_id(&link.name, &hub_reference.business_key_name);
+                hub_reference.source_columns.iter_mut().for_each(ColumnData::derive_id);
+                hub_reference.target_column.iter_mut().for_each(ColumnData::derive_id);
+            }
+            link.descriptors.iter_mut().for_each(Descriptor::derive_ids);
+        }
+    }
+}
+
+impl Descriptor {
+    fn derive_ids(&mut self) {
+        let descriptor_link = &mut self.descriptor_link;
+        descriptor_link.source_column.iter_mut().for_each(ColumnData::derive_id);
+        descriptor_link.target_column.iter_mut().for_each(ColumnData::derive_id);
+        self.id = descriptor_id(descriptor_link.source_column.as_ref(), &descriptor_link.alias);
+        descriptor_link.id = descriptor_link_id(&self.id);
+    }
+}
+
+impl ColumnData {
+    fn derive_id(&mut self) {
+        self.id = column_data_id(self.system_id, self.table_oid, self.column_ordinal_position);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BusinessKey {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
     #[serde(rename = "Name")]
     pub name: String,
@@ -26,11 +307,38 @@ pub struct BusinessKey {
     pub business_key_part_links: Vec<BusinessKeyPartLink>,
     #[serde(rename = "Descriptors")]
     pub descriptors: Vec<Descriptor>,
+    #[serde(rename = "Status Tracking", default)]
+    pub status_tracking: bool,
+}
+
+// A business key sourced from several tables lists, in each part link, one source column per table.
+impl BusinessKey {
+    // Tables the business key is sourced from, in the order their columns are listed.
+    pub fn source_tables(&self) -> Vec<(String, String)> {
+        let mut source_tables: Vec<(String, String)> = Vec::new();
+        for part_link in &self.business_key_part_links {
+            for source_column in &part_link.source_columns {
+                let source_table = (source_column.schema_name.clone(), source_column.table_name.clone());
+                if !source_tables.contains(&source_table) {
+                    source_tables.push(source_table);
+                }
+            }
+        }
+        source_tables
+    }
+
+    // Business key part columns of a source table, in part order.  None when a part is not sourced from the table.
+    pub fn source_columns_of(&self, schema_name: &str, table_name: &str) -> Option<Vec<&ColumnData>> {
+        self.business_key_part_links
+            .iter()
+            .map(|part_link| part_link.source_columns.iter().find(|source_column| source_column.schema_name == schema_name && source_column.table_name == table_name))
+            .collect()
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BusinessKeyPartLink {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
     #[serde(rename = "Alias")]
     pub alias: String,
@@ -40,9 +348,34 @@ pub struct BusinessKeyPartLink {
     pub target_column: Option<ColumnData>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Link {
+    #[serde(rename = "ID", default)]
+    pub id: Uuid,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Hub References")]
+    pub hub_references: Vec<LinkHubReference>,
+    #[serde(rename = "Descriptors", default)]
+    pub descriptors: Vec<Descriptor>,
+}
+
+// The source columns, from the link's source table, that hash to the referenced hub's key.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LinkHubReference {
+    #[serde(rename = "ID", default)]
+    pub id: Uuid,
+    #[serde(rename = "Business Key Name")]
+    pub business_key_name: String,
+    #[serde(rename = "Source Column Data")]
+    pub source_columns: Vec<ColumnData>,
+    #[serde(rename = "Target Column Data")]
+    pub target_column: Option<ColumnData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Descriptor {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
     #[serde(rename = "Descriptor Link")]
     pub descriptor_link: DescriptorLink,
@@ -52,9 +385,9 @@ pub struct Descriptor {
     pub is_sensitive: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DescriptorLink {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
     #[serde(rename = "Alias")]
     pub alias: String,
@@ -64,22 +397,22 @@ pub struct DescriptorLink {
     pub target_column: Option<ColumnData>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ColumnData {
-    #[serde(rename = "ID")]
+    #[serde(rename = "ID", default)]
     pub id: Uuid,
-    #[serde(rename = "System ID")]
+    #[serde(rename = "System ID", default)]
     pub system_id: i64,
     #[serde(rename = "Schema Name")]
     pub schema_name: String,
-    #[serde(rename = "Table OID")]
+    #[serde(rename = "Table OID", default)]
     pub table_oid: u32,
     #[serde(rename = "Table Name")]
     pub table_name: String,
     #[serde(rename = "Column Name")]
     pub column_name: String,
-    #[serde(rename = "Column Ordinal Position")]
+    #[serde(rename = "Column Ordinal Position", default)]
     pub column_ordinal_position: i16,
-    #[serde(rename = "Column Type")]
+    #[serde(rename = "Column Type", default)]
     pub column_type_name: String,
 }
\ No newline at end of file
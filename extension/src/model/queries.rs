@@ -1,10 +1,4 @@
-pub const SOURCE_TABLE_SAMPLE: &str = r#"
-    WITH Temp_Data (schema, "table", status, status_code, status_response) AS (
-        VALUES
-        ('PUBLIC', 'CUSTOMER',  'Skipped', 'SKIP', 'Source Table was skipped as column(s) need additional context. Please run the following SQL query for more information: SELECT schema, table, column, status, status_response FROM auto_dw.source_status_detail() WHERE schema = ''public'' AND table = ''customers''.')
-    )
-    SELECT * FROM Temp_Data;
-        "#;
+use pgrx::spi::{quote_identifier, quote_qualified_identifier};
 
 pub const SOURCE_OBJECTS_JSON: &str = r#"
             WITH
@@ -17,9 +11,22 @@ pub const SOURCE_OBJECTS_JSON: &str = r#"
                 LEFT JOIN auto_dw.transformer_responses AS t ON s.pk_source_objects = t.fk_source_objects
                 WHERE current_flag = 'Y' AND deleted_flag = 'N'
                 GROUP BY table_oid),
+            table_context_update_cal AS (
+                SELECT 
+                    s.table_oid, 
+                    MAX(c.updated_at) AS max_table_context_update
+                FROM auto_dw.source_objects AS s
+                JOIN auto_dw.context AS c ON 
+                    c.schema_name = s.schema_name AND
+                    (c.table_name IS NULL OR c.table_name = s.table_name) AND
+                    (c.column_name IS NULL OR c.column_name = s.column_name)
+                WHERE current_flag = 'Y' AND deleted_flag = 'N' AND c.context ?| ARRAY['sensitive', 'business_key', 'hint']
+                GROUP BY s.table_oid),
             tables_requiring_transformation AS (
-                SELECT DISTINCT table_oid FROM table_tranformation_time_cal
-                WHERE (max_table_update > max_table_transformer_generation) OR max_table_transformer_generation IS NULL
+                SELECT DISTINCT t.table_oid FROM table_tranformation_time_cal AS t
+                LEFT JOIN table_context_update_cal AS c ON t.table_oid = c.table_oid
+                WHERE (max_table_update > max_table_transformer_generation) OR max_table_transformer_generation IS NULL OR
+                      (max_table_context_update > max_table_transformer_generation)
             ),
             source_table_details AS (
                 SELECT s.*
@@ -61,9 +68,225 @@ pub const SOURCE_OBJECTS_JSON: &str = r#"
             ;
         "#;
 
-#[no_mangle]
-pub fn source_object_dw(schema_pattern_include: &str, table_pattern_include: &str, column_pattern_include: &str, schema_pattern_exclude: &str, table_pattern_exclude: &str, column_pattern_exclude: &str) -> String {
-    format!(r#"
+pub const FOREIGN_KEY_COLUMNS: &str = r#"
+		SELECT
+			pg_constraint.oid::OID AS constraint_oid,
+			pg_constraint.conrelid::OID AS table_oid,
+			pg_constraint.confrelid::OID AS referenced_table_oid,
+			fk_columns.column_ordinal_position::SMALLINT AS column_ordinal_position,
+			fk_columns.referenced_column_ordinal_position::SMALLINT AS referenced_column_ordinal_position
+		FROM pg_catalog.pg_constraint
+		CROSS JOIN LATERAL unnest(pg_constraint.conkey, pg_constraint.confkey) 
+			AS fk_columns(column_ordinal_position, referenced_column_ordinal_position)
+		WHERE pg_constraint.contype = 'f'
+		ORDER BY pg_constraint.oid, fk_columns.column_ordinal_position
+		;
+		"#;
+
+// Context for each column of a table, with hints from the schema, table, and column combined.
+pub const SOURCE_COLUMN_CONTEXT: &str = r#"
+		SELECT
+			s.column_ordinal_position::INT AS column_ordinal_position,
+			NULLIF(concat_ws(' ', schema_context.context->>'hint', table_context.context->>'hint', column_context.context->>'hint'), '') AS hint,
+			CASE jsonb_typeof(column_context.context->'business_key')
+				WHEN 'string' THEN TRUE
+				WHEN 'boolean' THEN (column_context.context->>'business_key')::BOOLEAN
+			END AS is_business_key,
+			CASE jsonb_typeof(column_context.context->'business_key')
+				WHEN 'string' THEN column_context.context->>'business_key'
+			END AS business_key_name,
+			(column_context.context->>'sensitive')::BOOLEAN AS is_sensitive
+		FROM auto_dw.source_objects AS s
+		LEFT JOIN auto_dw.context AS schema_context ON 
+			schema_context.schema_name = s.schema_name AND 
+			schema_context.table_name IS NULL
+		LEFT JOIN auto_dw.context AS table_context ON 
+			table_context.schema_name = s.schema_name AND 
+			table_context.table_name = s.table_name AND 
+			table_context.column_name IS NULL
+		LEFT JOIN auto_dw.context AS column_context ON 
+			column_context.schema_name = s.schema_name AND 
+			column_context.table_name = s.table_name AND 
+			column_context.column_name = s.column_name
+		WHERE s.table_oid = $1::OID AND s.current_flag = 'Y' AND s.deleted_flag = 'N'
+		;
+		"#;
+
+// Context keys set to null are removed.
+pub const UPSERT_CONTEXT: &str = r#"
+		INSERT INTO auto_dw.context (schema_name, table_name, column_name, context)
+		VALUES ($1, $2, $3, jsonb_strip_nulls($4::JSONB))
+		ON CONFLICT (schema_name, COALESCE(table_name, ''), COALESCE(column_name, ''))
+		DO UPDATE SET
+			context = jsonb_strip_nulls(context.context || $4::JSONB),
+			updated_at = (now() AT TIME ZONE 'UTC')
+		;
+		"#;
+
+pub const INSERT_TRANSFORMER_RESPONSE: &str = r#"
+		INSERT INTO auto_dw.transformer_responses (fk_source_objects, model_name, category, business_key_name, confidence_score, reason, manual_override)
+		VALUES ($1, $2, $3, $4, $5, $6, $7)
+		;
+		"#;
+
+pub const INSERT_LOG: &str = r#"
+		INSERT INTO auto_dw.log (process, level, message)
+		VALUES ($1, $2, $3)
+		;
+		"#;
+
+// Returns the number of source columns the category was set on, 0 when the column is not included for DW automation.
+pub const INSERT_MANUAL_OVERRIDE: &str = r#"
+		WITH manual_override AS (
+			INSERT INTO auto_dw.transformer_responses (fk_source_objects, model_name, category, business_key_name, confidence_score, reason, manual_override)
+			SELECT s.pk_source_objects, current_user, $4, $5, 1.0, $6, TRUE
+			FROM auto_dw.source_objects AS s
+			WHERE
+				s.schema_name = $1 AND s.table_name = $2 AND s.column_name = $3 AND
+				s.current_flag = 'Y' AND s.deleted_flag = 'N'
+			RETURNING 1
+		)
+		SELECT COUNT(*)::BIGINT AS manual_override_count FROM manual_override
+		;
+		"#;
+
+// Columns of a table whose latest response, across all versions of the column, is a manual override.
+pub const MANUAL_OVERRIDE_COLUMNS: &str = r#"
+		WITH column_response AS (
+			SELECT DISTINCT ON (s.column_ordinal_position)
+				s.column_ordinal_position::INT AS column_ordinal_position,
+				t.model_name,
+				t.category,
+				t.business_key_name,
+				t.reason,
+				t.manual_override
+			FROM auto_dw.source_objects AS s
+			JOIN auto_dw.source_objects AS column_version ON
+				column_version.schema_name = s.schema_name AND
+				column_version.table_name = s.table_name AND
+				column_version.column_name = s.column_name
+			JOIN auto_dw.transformer_responses AS t ON t.fk_source_objects = column_version.pk_source_objects
+			WHERE s.table_oid = $1::OID AND s.current_flag = 'Y' AND s.deleted_flag = 'N'
+			ORDER BY s.column_ordinal_position, t.pk_transformer_responses DESC
+		)
+		SELECT column_ordinal_position, model_name, category, business_key_name, reason
+		FROM column_response
+		WHERE manual_override
+		;
+		"#;
+
+// Builds recorded by go, from their build calls and schemas in the repo, oldest first.
+pub const BUILDS: &str = r#"
+		WITH build_call_cal AS (
+			SELECT
+				build_id,
+				MIN(created_at) AS build_ts
+			FROM auto_dw.build_call
+			GROUP BY build_id
+		)
+		SELECT
+			COALESCE(r.build_id, b.build_id)::TEXT AS build_id,
+			COALESCE(r.insert_time, b.build_ts)::TEXT AS build_ts,
+			r.rolled_back_at::TEXT AS rolled_back_at,
+			r.schema AS schema
+		FROM auto_dw.dv_repo AS r
+		FULL JOIN build_call_cal AS b ON r.build_id = b.build_id
+		ORDER BY COALESCE(r.insert_time, b.build_ts)
+		;
+		"#;
+
+pub const ROLLBACK_BUILD: &str = r#"
+		UPDATE auto_dw.dv_repo SET rolled_back_at = (now() AT TIME ZONE 'UTC') WHERE build_id = $1;
+		"#;
+
+pub const ROLLBACK_BUILD_CALL: &str = r#"
+		UPDATE auto_dw.build_call SET build_status = 'Rolled Back' WHERE build_id = $1;
+		"#;
+
+pub const TABLE_COLUMNS: &str = r#"
+		SELECT
+			pg_attribute.attname::TEXT AS column_name,
+			pg_catalog.format_type(pg_attribute.atttypid, pg_attribute.atttypmod) AS column_type_name
+		FROM pg_catalog.pg_attribute
+		WHERE 
+			pg_attribute.attrelid = to_regclass(format('%I.%I', $1, $2))
+			AND pg_attribute.attnum > 0  -- Only real columns, not system columns
+			AND NOT pg_attribute.attisdropped  -- Only columns that are not dropped
+		;
+		"#;
+
+// Source columns of a table the transformer has yet to respond to.
+pub const PENDING_TRANSFORMER_COLUMNS: &str = r#"
+		SELECT COUNT(*)::BIGINT AS pending_transformer_columns
+		FROM auto_dw.source_objects AS s
+		WHERE 
+			s.schema_name = $1 AND s.table_name = $2 AND
+			s.current_flag = 'Y' AND s.deleted_flag = 'N' AND
+			NOT EXISTS (SELECT 1 FROM auto_dw.transformer_responses AS t WHERE t.fk_source_objects = s.pk_source_objects)
+		;
+		"#;
+
+// Errors over the last day logged against a DW table, with its name matched as a whole identifier, or from scheduled
+// loads of its source tables and schemas.  Characters of the name other than letters, digits, and _ are escaped.
+pub const RECENT_ERRORS: &str = r#"
+		WITH errors AS (
+			SELECT log_ts AS error_ts, message
+			FROM auto_dw.log
+			WHERE upper(level) = 'ERROR' AND message ~ ('(^|[^[:alnum:]_$])' || regexp_replace($1, '([^[:alnum:]_])', '\\\1', 'g') || '($|[^[:alnum:]_$])')
+			UNION ALL
+			SELECT finished_at AS error_ts, message
+			FROM auto_dw.load_run
+			WHERE status = 'Failed' AND (
+				(table_name IS NULL AND schema_name::TEXT = ANY($2)) OR
+				(schema_name || '.' || table_name) = ANY($3))
+		)
+		SELECT 
+			COUNT(*)::BIGINT AS recent_errors,
+			(array_agg(message ORDER BY error_ts DESC))[1] AS last_error
+		FROM errors
+		WHERE error_ts > (now() AT TIME ZONE 'UTC') - INTERVAL '1 day'
+		;
+		"#;
+
+pub const LOAD_SCHEDULES: &str = r#"
+		SELECT
+			schema_name::TEXT AS schema_name,
+			table_name::TEXT AS table_name,
+			context->>'cron' AS cron
+		FROM auto_dw.context
+		WHERE column_name IS NULL AND context ? 'cron'
+		ORDER BY schema_name, table_name NULLS FIRST
+		;
+		"#;
+
+pub const INSERT_LOAD_RUN: &str = r#"
+		INSERT INTO auto_dw.load_run (schema_name, table_name, cron, build_id, status, message, started_at, finished_at)
+		VALUES ($1, $2, $3, $4, $5, $6, $7::TIMESTAMP, $8::TIMESTAMP)
+		;
+		"#;
+
+// Latest load time of a DW table along with the rows it loaded.  All rows of a load share its load time.
+pub fn table_load_stats(schema_name: &str, table_name: &str, load_ts_column: &str, has_record_source: bool) -> String {
+	let system_filter = if has_record_source { "WHERE record_source <> 'SYSTEM'" } else { "" };
+	let load_ts_column = quote_identifier(load_ts_column);
+	let table = quote_qualified_identifier(schema_name, table_name);
+	format!(r#"
+		WITH loads AS (
+			SELECT {load_ts_column} AS load_ts
+			FROM {table}
+			{system_filter}
+		)
+		SELECT 
+			MAX(load_ts)::TEXT AS last_load_ts,
+			COUNT(*) FILTER (WHERE load_ts = (SELECT MAX(load_ts) FROM loads))::BIGINT AS rows_last_load
+		FROM loads
+		;
+		"#)
+}
+
+// Refreshes auto_dw.source_objects.  Columns matching the include patterns $1 (schema), $2 (table), and $3 (column)
+// are added and those matching the exclude patterns $4, $5, and $6 are removed.  'a^' matches nothing.
+pub const SOURCE_OBJECT_DW: &str = r#"
 DROP TABLE IF EXISTS temp_source_objects;
 
 CREATE TEMPORARY TABLE temp_source_objects AS
@@ -178,9 +401,9 @@ table_source_list AS (
 	FROM source_objects_prep
 	-- 'a^' ~ mach nothing.
 	WHERE 
-		schema_name ~ '{}' AND
-		table_name ~ '{}' AND
-		column_name ~ '{}'
+		schema_name ~ $1 AND
+		table_name ~ $2 AND
+		column_name ~ $3
 	--- Removing Schemas
 	EXCEPT
 	SELECT
@@ -189,9 +412,9 @@ table_source_list AS (
 		column_ordinal_position
 	FROM source_objects_prep
 	WHERE 
-		schema_name ~ '{}' AND
-		table_name ~ '{}' AND
-		column_name ~ '{}'
+		schema_name ~ $4 AND
+		table_name ~ $5 AND
+		column_name ~ $6
 )
 SELECT
 source_objects_prep.schema_oid,
@@ -317,17 +540,14 @@ LEFT JOIN auto_dw.source_objects ON source_objects.current_flag = 'Y'
 WHERE source_objects.column_ordinal_position IS NULL;
 
 DROP TABLE IF EXISTS temp_source_objects;
-"#, schema_pattern_include, table_pattern_include, column_pattern_include, schema_pattern_exclude, table_pattern_exclude, column_pattern_exclude)
-}
+"#;
 
-#[no_mangle]
-pub fn insert_into_build_call(
-	build_id: &str, build_flag: &str, build_status: &str, status: &str, accepted_transformer_confidence_level: &str
-) -> String {
-    format!(r#"
+// Records the columns with status $4 against build $1, flagged $2 with build status $3.  $5 is the accepted
+// transformer confidence level.
+pub const INSERT_INTO_BUILD_CALL: &str = r#"
     INSERT INTO auto_dw.build_call (fk_transformer_responses, build_id, build_flag, build_status)
 	WITH
-	confidence_level AS (SELECT {accepted_transformer_confidence_level} AS value),
+	confidence_level AS (SELECT $5::NUMERIC AS value),
 	source_objects_tranformation_cal AS (
 		SELECT 
 			MAX(pk_transformer_responses)AS max_pk_transformer_response
@@ -370,11 +590,7 @@ pub fn insert_into_build_call(
 		SELECT *,
 				CASE
 					WHEN confidence_score IS NULL THEN 'Queued for Processing'
-					-- Links
-					WHEN category = 'Business Key Part' AND confidence_score >= cl.value 					AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Link Implementation'
-					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 0 	AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Link Implementation'
-					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 1 	AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Business Key (BK), Awaiting Link Implementation'
-					-- Hubs
+					-- Hubs and Links
 					WHEN category = 'Business Key Part' AND confidence_score >= cl.value 										THEN 'Ready to Deploy'
 					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 0 						THEN 'Ready to Deploy'
 					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 1 						THEN 'Ready to Deploy - Awaiting Business Key (BK)'
@@ -406,17 +622,15 @@ pub fn insert_into_build_call(
 	)
 	SELECT 
 		pk_transformer_responses AS fk_transformer_responses,
-		'{build_id}' AS build_id,
-		'{build_flag}' AS build_flag,
-		'{build_status}' AS build_status
+		$1 AS build_id,
+		$2 AS build_flag,
+		$3 AS build_status
 	FROM source_object
-	WHERE status = '{status}';
-"#)
-}
+	WHERE status = $4;
+"#;
 
-#[no_mangle]
-pub fn build_object_pull(build_id: &str) -> String {
-    format!(r#"
+// Source columns recorded against build $1.
+pub const BUILD_OBJECT_PULL: &str = r#"
 		WITH system AS (
 			SELECT system_identifier AS id FROM pg_control_system() LIMIT 1
 		)
@@ -429,21 +643,122 @@ pub fn build_object_pull(build_id: &str) -> String {
 		column_type_name::TEXT AS column_type_name, 
 		system.id::BIGINT AS system_id,
 		so.table_oid::OID as table_oid,
-		so.column_ordinal_position::SMALLINT AS column_ordinal_position
+		so.column_ordinal_position::SMALLINT AS column_ordinal_position,
+		column_context.context->>'alias' AS alias
 		FROM system, auto_dw.build_call AS bc
 		LEFT JOIN auto_dw.transformer_responses AS t ON bc.fk_transformer_responses = t.pk_transformer_responses
 		LEFT JOIN auto_dw.source_objects AS so ON t.fk_source_objects = so.pk_source_objects
-		WHERE build_id = '{}';
-		"#, build_id)
-}
+		LEFT JOIN auto_dw.context AS column_context ON 
+			column_context.schema_name = so.schema_name AND 
+			column_context.table_name = so.table_name AND 
+			column_context.column_name = so.column_name
+		WHERE build_id = $1
+		ORDER BY so.table_oid, so.column_ordinal_position;
+		"#;
 
 #[no_mangle]
 pub fn source_column(accepted_transformer_confidence_level: &str) -> String {
+	let source_column_status = source_column_status(accepted_transformer_confidence_level);
+    format!(r#"
+		SELECT
+		schema,
+		"table",
+		"column",
+		status,
+		confidence_level,
+		status_response,
+		deployment_status
+		FROM ({source_column_status}) AS source_column
+		ORDER BY schema, "table", column_ordinal_position
+		;
+		"#)
+}
+
+// Table statuses rolled up from their column statuses, along with the columns holding each table back.
+#[no_mangle]
+pub fn source_table(accepted_transformer_confidence_level: &str) -> String {
+	let source_column_status = source_column_status(accepted_transformer_confidence_level);
+    format!(r#"
+        WITH
+		source_column AS ({source_column_status}),
+		source_table_cal AS (
+			SELECT
+				schema,
+				"table",
+				COUNT(*) AS column_count,
+				COUNT(*) FILTER (WHERE status = 'Queued for Processing') AS queued_count,
+				COUNT(*) FILTER (WHERE status = 'Ready to Deploy' AND deployment_status = 'Not Deployed') AS ready_to_deploy_count,
+				COUNT(*) FILTER (WHERE status = 'Ready to Deploy - Awaiting Business Key (BK)') AS awaiting_bk_count,
+				COUNT(*) FILTER (WHERE deployment_status = 'Awaiting Link') AS awaiting_link_count,
+				COUNT(*) FILTER (WHERE status = 'Requires Attention') AS requires_attention_count,
+				COUNT(*) FILTER (WHERE deployment_status = 'Deployed') AS deployed_count,
+				string_agg("column", ', ' ORDER BY column_ordinal_position) FILTER (WHERE status = 'Requires Attention') AS requires_attention_columns,
+				string_agg("column", ', ' ORDER BY column_ordinal_position) FILTER (WHERE deployment_status = 'Awaiting Link') AS awaiting_link_columns
+			FROM source_column
+			GROUP BY schema, "table"
+		),
+		source_table_status AS (
+			SELECT *,
+				CASE
+					WHEN requires_attention_count > 0 	THEN 'Requires Attention'
+					WHEN queued_count > 0 				THEN 'Queued for Processing'
+					WHEN awaiting_bk_count > 0 			THEN 'Awaiting Business Key (BK)'
+					WHEN awaiting_link_count > 0 		THEN 'Awaiting Link'
+					WHEN deployed_count = column_count 	THEN 'Deployed'
+					ELSE 'Ready to Deploy'
+				END AS status
+			FROM source_table_cal
+		)
+		SELECT
+		schema,
+		"table",
+		status,
+		CASE status
+			WHEN 'Requires Attention' 			THEN 'SKIP'
+			WHEN 'Queued for Processing' 		THEN 'QUEUED'
+			WHEN 'Awaiting Business Key (BK)' 	THEN 'AWAIT_BK'
+			WHEN 'Awaiting Link' 				THEN 'AWAIT_LINK'
+			WHEN 'Deployed' 					THEN 'DEPLOYED'
+			ELSE 'RTD'
+		END AS status_code,
+		CASE status
+			WHEN 'Requires Attention' THEN
+				'Source Table was skipped as column(s) need additional context: ' || requires_attention_columns || '.  ' ||
+				'Please run the following SQL query for more information: ' ||
+				'SELECT "column", status, status_response FROM auto_dw.source_column() ' ||
+				'WHERE schema = ' || quote_literal(schema) || ' AND "table" = ' || quote_literal("table") || ';'
+			WHEN 'Queued for Processing' THEN
+				queued_count || ' of ' || column_count || ' column(s) are queued for processing by the transformer.'
+			WHEN 'Awaiting Business Key (BK)' THEN
+				'Descriptors are ready to deploy, but a business key part has not been identified with enough confidence.'
+			WHEN 'Awaiting Link' THEN
+				'Foreign key column(s) ' || awaiting_link_columns || ' reference a table without a business key ready to deploy.'
+			WHEN 'Deployed' THEN
+				'All ' || column_count || ' column(s) are deployed to the DW.'
+			ELSE
+				ready_to_deploy_count || ' column(s) are ready to deploy' ||
+				CASE WHEN deployed_count > 0 THEN ' and ' || deployed_count || ' column(s) are deployed' ELSE '' END || '.'
+		END AS status_response,
+		column_count,
+		queued_count,
+		ready_to_deploy_count,
+		awaiting_bk_count,
+		awaiting_link_count,
+		requires_attention_count,
+		deployed_count
+		FROM source_table_status
+		ORDER BY schema, "table"
+		;
+		"#)
+}
+
+// Column statuses shared by source_column and source_table.
+fn source_column_status(accepted_transformer_confidence_level: &str) -> String {
     format!(r#"
         WITH
 		confidence_level AS (SELECT {accepted_transformer_confidence_level} AS value),
 		source_objects_tranformation_cal AS (
-			SELECT 
+			SELECT
 				MAX(pk_transformer_responses)AS max_pk_transformer_response
 			FROM auto_dw.transformer_responses AS t
 			GROUP BY fk_source_objects
@@ -453,8 +768,9 @@ pub fn source_column(accepted_transformer_confidence_level: &str) -> String {
 			JOIN source_objects_tranformation_cal AS c ON t.pk_transformer_responses = c.max_pk_transformer_response
 		),
 		source_object_status_prep AS (
-			SELECT 
+			SELECT
 				t.pk_transformer_responses,
+				s.table_oid,
 				s.schema_name,
 				s.table_name,
 				s.column_name,
@@ -463,16 +779,17 @@ pub fn source_column(accepted_transformer_confidence_level: &str) -> String {
 				t.reason,
 				t.category,
 				t.model_name,
+				t.manual_override,
 				MAX(
 				CASE
 					WHEN t.category = 'Business Key Part' AND t.confidence_score < cl.value THEN 1
-					ELSE 0 				  
+					ELSE 0
 				END
 				) OVER (PARTITION BY s.schema_name, s.table_name) AS bk_hold,
 			SUM(
 				CASE
 					WHEN t.category = 'Business Key Part' THEN 1
-					ELSE 0 				  
+					ELSE 0
 				END
 			) OVER (PARTITION BY s.schema_name, s.table_name) AS bkp_cnt
 			FROM auto_dw.source_objects AS s
@@ -480,27 +797,71 @@ pub fn source_column(accepted_transformer_confidence_level: &str) -> String {
 			LEFT JOIN source_object_transformation_latest AS t ON s.pk_source_objects = t.fk_source_objects
 			WHERE s.current_flag = 'Y' AND s.deleted_flag = 'N'
 		),
+		-- Source columns of the latest build of each DW schema.
+		deployed_column AS (
+			SELECT DISTINCT
+				source_column->>'Schema Name' AS schema_name,
+				source_column->>'Table Name' AS table_name,
+				source_column->>'Column Name' AS column_name
+			FROM (
+				SELECT DISTINCT ON (schema->>'DW Schema') schema
+				FROM auto_dw.dv_repo
+				WHERE rolled_back_at IS NULL
+				ORDER BY schema->>'DW Schema', insert_time DESC
+			) AS latest_dv_repo
+			CROSS JOIN LATERAL jsonb_path_query(latest_dv_repo.schema::JSONB, 'lax $.**."Source Column Data"[*]') AS source_column
+		),
+		-- Tables with a business key part ready to become a hub.
+		hub_ready_table AS (
+			SELECT DISTINCT table_oid
+			FROM source_object_status_prep
+			JOIN confidence_level AS cl ON true
+			WHERE category = 'Business Key Part' AND confidence_score >= cl.value
+		),
+		-- Foreign key columns referencing a table that cannot yet become a hub.
+		awaiting_link_column AS (
+			SELECT DISTINCT
+				pg_constraint.conrelid AS table_oid,
+				fk_column.column_ordinal_position
+			FROM pg_catalog.pg_constraint
+			CROSS JOIN LATERAL unnest(pg_constraint.conkey) AS fk_column(column_ordinal_position)
+			LEFT JOIN hub_ready_table ON pg_constraint.confrelid = hub_ready_table.table_oid
+			WHERE pg_constraint.contype = 'f' AND hub_ready_table.table_oid IS NULL
+		),
 		source_object AS (
-			SELECT *,
+			SELECT p.*,
 					CASE
 					WHEN confidence_score IS NULL THEN 'Queued for Processing'
-					-- Links
-					WHEN category = 'Business Key Part' AND confidence_score >= cl.value 					AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Link Implementation'
-					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 0 	AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Link Implementation'
-					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 1 	AND bkp_cnt > 1 	THEN 'Ready to Deploy - Awaiting Business Key (BK), Awaiting Link Implementation'
-					-- Hubs
+					-- Hubs and Links
 					WHEN category = 'Business Key Part' AND confidence_score >= cl.value 										THEN 'Ready to Deploy'
 					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 0 						THEN 'Ready to Deploy'
 					WHEN category <> 'Business Key Part' AND confidence_score >= cl.value AND bk_hold = 1 						THEN 'Ready to Deploy - Awaiting Business Key (BK)'
 		
 					ELSE 'Requires Attention'
 				END AS status,
-					CASE 
+				-- Whether a column ready to deploy is part of the latest DW build, or held back by a foreign key.
+				CASE
+					WHEN confidence_score >= cl.value AND d.column_name IS NOT NULL 	THEN 'Deployed'
+					WHEN confidence_score >= cl.value AND a.table_oid IS NOT NULL 		THEN 'Awaiting Link'
+					ELSE 'Not Deployed'
+				END AS deployment_status,
+					CASE
 						WHEN confidence_score IS NOT NULL THEN CONCAT((confidence_score * 100)::INT::TEXT, '%')
 						ELSE '-'
 					END AS confidence_level,
-					CASE 
-						WHEN confidence_score IS NOT NULL THEN 
+					CASE
+						WHEN manual_override THEN
+							(
+							'Status: ' ||
+							CASE
+								WHEN category = 'Business Key Part' OR bk_hold = 0 THEN 'Ready to Deploy'
+								ELSE 'Ready to Deploy - Awaiting Business Key (BK)'
+							END || ': ' ||
+							'Manual Override: ' || model_name ||
+							' set this column as a ' || category || '.  ' ||
+							'Reason: ' || reason
+							)
+						WHEN confidence_score IS NOT NULL THEN
 							(
 							'Status: ' ||
 							CASE
@@ -508,32 +869,38 @@ pub fn source_column(accepted_transformer_confidence_level: &str) -> String {
 								WHEN confidence_score >= cl.value THEN 'Ready to Deploy'
 								ELSE 'Requires Attention'
 							END || ': ' ||
-							'Model: ' || model_name || 
-							' categorized this column as a ' || category || 
+							'Model: ' || model_name ||
+							' categorized this column as a ' || category ||
 							' with a confidence of ' || CONCAT((confidence_score * 100)::INT::TEXT, '%') || '.  ' ||
 							'Model Reasoning: ' || reason
 							)
 						ELSE '-'
 					END AS status_response
-			FROM source_object_status_prep
+			FROM source_object_status_prep AS p
 			JOIN confidence_level AS cl ON true
+			LEFT JOIN deployed_column AS d ON
+				p.schema_name = d.schema_name AND
+				p.table_name = d.table_name AND
+				p.column_name = d.column_name
+			LEFT JOIN awaiting_link_column AS a ON
+				p.table_oid = a.table_oid AND
+				p.column_ordinal_position = a.column_ordinal_position
 		)
-		SELECT 
+		SELECT
 		schema_name::TEXT AS schema,
 		table_name::TEXT AS table,
 		column_name::TEXT AS column,
+		column_ordinal_position,
 		status,
 		confidence_level,
-		status_response
+		status_response,
+		deployment_status
 		FROM source_object
-		ORDER BY schema_name, table_name, column_ordinal_position
-		;
 		"#)
 }
 
-#[no_mangle]
-pub fn get_column_data(schema_name: &str, table_name: &str, column_name: &str) -> String {
-    format!(r#"
+// Column data for column $3 of table $2 in schema $1.
+pub const GET_COLUMN_DATA: &str = r#"
 		WITH 
 		system_qry AS (
 			SELECT system_identifier AS id FROM pg_control_system() LIMIT 1
@@ -642,9 +1009,8 @@ pub fn get_column_data(schema_name: &str, table_name: &str, column_name: &str) -
 			source_objects_prep.column_type_name::TEXT AS column_type_name 
 		FROM source_objects_prep, system_qry
 		WHERE
-		schema_name = '{}' AND
-		table_name = '{}' AND
-		column_name = '{}'
+		schema_name = $1 AND
+		table_name = $2 AND
+		column_name = $3
 		;
-		"#, schema_name, table_name, column_name)
-}
\ No newline at end of file
+		"#;
\ No newline at end of file
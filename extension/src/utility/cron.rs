@@ -0,0 +1,184 @@
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+// A five field cron expression: minute, hour, day of month, month, and day of week.
+// Each field accepts *, a value, a range (a-b), or a list (a,b), with an optional step (/n).
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<CronSchedule, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+
+        if fields.len() != 5 {
+            return Err(format!("Cron expression \"{}\" must have 5 fields.", expression));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7)?;
+        // Both 0 and 7 are Sunday.
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+
+        Ok(CronSchedule {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            days_of_month_restricted: fields[2] != "*",
+            days_of_week_restricted: fields[4] != "*",
+        })
+    }
+
+    // As with cron, when both day fields are restricted a match on either day field is enough.
+    pub fn matches(&self, date_time: &NaiveDateTime) -> bool {
+        let day_of_month = self.days_of_month[date_time.day() as usize];
+        let day_of_week = self.days_of_week[date_time.weekday().num_days_from_sunday() as usize];
+
+        let day = if self.days_of_month_restricted && self.days_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+
+        self.minutes[date_time.minute() as usize] &&
+        self.hours[date_time.hour() as usize] &&
+        self.months[date_time.month() as usize] &&
+        day
+    }
+}
+
+// Values allowed by a field, indexed by value.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut allowed = vec![false; max as usize + 1];
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().map_err(|_| format!("Invalid cron step \"{}\".", part))?;
+                if step == 0 {
+                    return Err(format!("Invalid cron step \"{}\".", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            // A stepped single value, such as 5/15, runs from the value to the end of the field.
+            if part.contains('/') { (value, max) } else { (value, value) }
+        };
+
+        if start > end {
+            return Err(format!("Invalid cron range \"{}\".", part));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            allowed[value as usize] = true;
+        }
+    }
+
+    Ok(allowed)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(value) if value >= min && value <= max => Ok(value),
+        _ => Err(format!("Cron value \"{}\" must be between {} and {}.", value, min, max)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn allowed_values(field: &str, min: u32, max: u32) -> Vec<u32> {
+        parse_field(field, min, max).unwrap()
+            .iter()
+            .enumerate()
+            .filter(|(_, allowed)| **allowed)
+            .map(|(value, _)| value as u32)
+            .collect()
+    }
+
+    #[test]
+    fn parses_values_ranges_lists_and_steps() {
+        assert_eq!(vec![5], allowed_values("5", 0, 59));
+        assert_eq!(vec![1, 2, 3], allowed_values("1-3", 0, 59));
+        assert_eq!(vec![1, 15, 30], allowed_values("1,15,30", 0, 59));
+        assert_eq!(vec![0, 15, 30, 45], allowed_values("*/15", 0, 59));
+        assert_eq!(vec![10, 12, 14], allowed_values("10-15/2", 0, 59));
+        assert_eq!(vec![5, 20, 35, 50], allowed_values("5/15", 0, 59));
+        assert_eq!(vec![1, 3, 5, 20, 21], allowed_values("1-5/2,20,21", 0, 59));
+        assert_eq!((1..=12).collect::<Vec<u32>>(), allowed_values("*", 1, 12));
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert!(parse_field("60", 0, 59).is_err());
+        assert!(parse_field("0", 1, 31).is_err());
+        assert!(parse_field("5-70", 0, 59).is_err());
+        assert!(parse_field("10-5", 0, 59).is_err());
+        assert!(parse_field("*/0", 0, 59).is_err());
+        assert!(parse_field("*/x", 0, 59).is_err());
+        assert!(parse_field("a", 0, 59).is_err());
+        assert!(parse_field("", 0, 59).is_err());
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("* * * * * *").is_err());
+        assert!(CronSchedule::parse("* * * 13 *").is_err());
+        assert!(CronSchedule::parse("* * * * 8").is_err());
+    }
+
+    #[test]
+    fn matches_minutes_hours_and_months() {
+        let schedule = CronSchedule::parse("*/15 9-17 * 1,7 *").unwrap();
+        assert!(schedule.matches(&at(2024, 1, 10, 9, 0)));
+        assert!(schedule.matches(&at(2024, 7, 10, 17, 45)));
+        assert!(!schedule.matches(&at(2024, 1, 10, 9, 5)));
+        assert!(!schedule.matches(&at(2024, 1, 10, 18, 0)));
+        assert!(!schedule.matches(&at(2024, 2, 10, 9, 0)));
+    }
+
+    #[test]
+    fn matches_sunday_as_0_or_7() {
+        // 2024-01-07 is a Sunday.
+        assert!(CronSchedule::parse("0 0 * * 0").unwrap().matches(&at(2024, 1, 7, 0, 0)));
+        assert!(CronSchedule::parse("0 0 * * 7").unwrap().matches(&at(2024, 1, 7, 0, 0)));
+        assert!(!CronSchedule::parse("0 0 * * 7").unwrap().matches(&at(2024, 1, 8, 0, 0)));
+    }
+
+    #[test]
+    fn matches_either_day_field_when_both_are_restricted() {
+        // 2024-01-15 is a Monday; 2024-01-10 and 2024-01-11 are a Wednesday and Thursday.
+        let schedule = CronSchedule::parse("0 0 10 * 1").unwrap();
+        assert!(schedule.matches(&at(2024, 1, 10, 0, 0)));
+        assert!(schedule.matches(&at(2024, 1, 15, 0, 0)));
+        assert!(!schedule.matches(&at(2024, 1, 11, 0, 0)));
+
+        // With one day field restricted, both must match.
+        let schedule = CronSchedule::parse("0 0 10 * *").unwrap();
+        assert!(schedule.matches(&at(2024, 1, 10, 0, 0)));
+        assert!(!schedule.matches(&at(2024, 1, 15, 0, 0)));
+        let schedule = CronSchedule::parse("0 0 * * 1").unwrap();
+        assert!(schedule.matches(&at(2024, 1, 15, 0, 0)));
+        assert!(!schedule.matches(&at(2024, 1, 10, 0, 0)));
+    }
+}
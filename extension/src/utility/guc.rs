@@ -30,6 +30,22 @@ pub static PG_AUTO_DW_MODEL: GucSetting<Option<&CStr>> = GucSetting::<Option<&CS
 // The accepted transformer's, self-described, confidence level - default 0.8.
 pub static PG_AUTO_DW_ACCEPTED_TRANSFORMER_CONFIDENCE_LEVEL: GucSetting<f64> = GucSetting::<f64>::new(0.8);
 
+// Status tracking satellites are off by default.
+pub static PG_AUTO_DW_STATUS_TRACKING: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+// Default set to Data Vault
+pub static PG_AUTO_DW_DW_MODEL: GucSetting<Option<&CStr>> = GucSetting::<Option<&CStr>>::new(Some(unsafe {
+    CStr::from_bytes_with_nul_unchecked(b"data_vault\0")
+}));
+
+// PIT and bridge tables are snapshotted daily by default.
+pub static PG_AUTO_DW_PIT_SNAPSHOT_INTERVAL: GucSetting<Option<&CStr>> = GucSetting::<Option<&CStr>>::new(Some(unsafe {
+    CStr::from_bytes_with_nul_unchecked(b"1 day\0")
+}));
+
+// Default not set, DW objects are named hub_<name>, sat_<name>, <table>_hk, and so on.
+pub static PG_AUTO_DW_NAMING_TEMPLATE: GucSetting<Option<&CStr>> = GucSetting::<Option<&CStr>>::new(None);
+
 pub fn init_guc() {
     // Register the GUCs
     GucRegistry::define_string_guc(
@@ -97,6 +113,42 @@ pub fn init_guc() {
         GucFlags::default(),
     );
 
+    GucRegistry::define_bool_guc(
+        "pg_auto_dw.status_tracking",
+        "Status tracking satellites for the pg_auto_dw extension.",
+        "Specifies whether hubs built by the pg_auto_dw extension get a status satellite recording when business keys are deleted from, or reappear in, their source table.",
+        &PG_AUTO_DW_STATUS_TRACKING,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_string_guc(
+        "pg_auto_dw.dw_model",
+        "Data warehouse model for the pg_auto_dw extension.",
+        "Specifies the model of the tables built by the pg_auto_dw extension.  Current available models include, data_vault and kimball.",
+        &PG_AUTO_DW_DW_MODEL,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_string_guc(
+        "pg_auto_dw.pit_snapshot_interval",
+        "PIT and bridge snapshot interval for the pg_auto_dw extension.",
+        "Specifies the interval, such as '1 day' or '1 hour', between the snapshots taken when the pg_auto_dw extension refreshes its PIT and bridge tables.",
+        &PG_AUTO_DW_PIT_SNAPSHOT_INTERVAL,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_string_guc(
+        "pg_auto_dw.naming_template",
+        "Naming template for the pg_auto_dw extension.",
+        "Specifies a JSON object of templates, such as {\"hub\": \"h_{name}\"}, naming the tables and columns built by the pg_auto_dw extension.",
+        &PG_AUTO_DW_NAMING_TEMPLATE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
 }
 
 // For handling of GUCs that can be error prone
@@ -109,6 +161,10 @@ pub enum PgAutoDWGuc {
     TransformerServerToken,
     Model,
     AcceptedTransformerConfidenceLevel,
+    StatusTracking,
+    DwModel,
+    PitSnapshotInterval,
+    NamingTemplate,
 }
 
 
@@ -122,6 +178,10 @@ pub fn get_guc(guc: PgAutoDWGuc) -> Option<String> {
         PgAutoDWGuc::TransformerServerToken => cstr_option_to_string(PG_AUTO_DW_TRANSFORMER_SERVER_TOKEN.get()),
         PgAutoDWGuc::Model => cstr_option_to_string(PG_AUTO_DW_MODEL.get()),
         PgAutoDWGuc::AcceptedTransformerConfidenceLevel => cstr_from_float(PG_AUTO_DW_ACCEPTED_TRANSFORMER_CONFIDENCE_LEVEL.get()),
+        PgAutoDWGuc::StatusTracking => Some(PG_AUTO_DW_STATUS_TRACKING.get().to_string()),
+        PgAutoDWGuc::DwModel => cstr_option_to_string(PG_AUTO_DW_DW_MODEL.get()),
+        PgAutoDWGuc::PitSnapshotInterval => cstr_option_to_string(PG_AUTO_DW_PIT_SNAPSHOT_INTERVAL.get()),
+        PgAutoDWGuc::NamingTemplate => cstr_option_to_string(PG_AUTO_DW_NAMING_TEMPLATE.get()),
     }
 }
 
@@ -2,4 +2,5 @@ pub mod transformer_client;
 mod ollama_client;
 mod openai_client;
 pub mod setup;
-pub mod guc;
\ No newline at end of file
+pub mod guc;
+pub mod cron;
\ No newline at end of file
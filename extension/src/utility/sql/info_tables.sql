@@ -36,6 +36,7 @@ CREATE TABLE IF NOT EXISTS transformer_responses
     business_key_name TEXT,
     confidence_score NUMERIC(3, 2),
     reason TEXT,
+    manual_override BOOLEAN DEFAULT FALSE, -- Set with auto_dw.set_column_category
     created_at TIMESTAMP WITHOUT TIME ZONE DEFAULT (now() AT TIME ZONE 'UTC'),
     CONSTRAINT fk_source_objects FOREIGN KEY (fk_source_objects) 
 	   	REFERENCES source_objects(pk_source_objects)
@@ -62,6 +63,7 @@ DROP TABLE IF EXISTS dv_repo;
 CREATE TABLE dv_repo (
     build_id TEXT,
     insert_time  TIMESTAMP WITHOUT TIME ZONE DEFAULT (now() AT TIME ZONE 'UTC'),
+    rolled_back_at TIMESTAMP WITHOUT TIME ZONE, -- Set by go('Rollback', build_id)
     schema JSON
 );
 
@@ -74,3 +76,34 @@ CREATE TABLE log (
 	level VARCHAR(50),
     message TEXT     
 );
+
+DROP TABLE IF EXISTS context;
+
+CREATE TABLE IF NOT EXISTS context
+(
+    pk_context BIGSERIAL PRIMARY KEY,
+    schema_name name NOT NULL,
+    table_name name,     -- NULL for schema context
+    column_name name,    -- NULL for schema and table context
+    context JSONB,
+    created_at TIMESTAMP WITHOUT TIME ZONE DEFAULT (now() AT TIME ZONE 'UTC'),
+    updated_at TIMESTAMP WITHOUT TIME ZONE DEFAULT (now() AT TIME ZONE 'UTC')
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS context_object_idx
+    ON context (schema_name, COALESCE(table_name, ''), COALESCE(column_name, ''));
+
+DROP TABLE IF EXISTS load_run;
+
+CREATE TABLE IF NOT EXISTS load_run
+(
+    pk_load_run BIGSERIAL PRIMARY KEY,
+    schema_name name,
+    table_name name,     -- NULL for schema loads
+    cron TEXT,
+    build_id TEXT,
+    status VARCHAR(50),
+    message TEXT,
+    started_at TIMESTAMP WITHOUT TIME ZONE,
+    finished_at TIMESTAMP WITHOUT TIME ZONE
+);